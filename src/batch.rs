@@ -0,0 +1,209 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::srt::SRT;
+
+/// Runs the read/clean/write pipeline on a single subtitle file.
+///
+/// Invalid subtitles are dropped while reading the file, see [`SRT::read_file`].
+///
+/// # Arguments
+///
+/// * `input_path` - The path to the SRT file to clean.
+/// * `output_path` - The path to write the cleaned SRT file to.
+///
+/// # Returns
+///
+/// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+pub fn process_file(input_path: &str, output_path: &str) -> Result<usize, String> {
+    let mut srt = SRT::new(input_path);
+    srt.read_file()
+        .map_err(|e| format!("{}: {}", input_path, e))?;
+    srt.write_file(output_path)
+        .map_err(|e| format!("{}: {}", output_path, e))
+}
+
+/// Finds the `.srt` files in a directory, sorted by name. Subdirectories are not searched.
+///
+/// # Arguments
+///
+/// * `input_dir` - The directory to search.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, String>` - The paths of the SRT files, or an error message if the directory can't be read.
+pub fn find_srt_files(input_dir: &str) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(input_dir)
+        .map_err(|e| format!("Unable to read directory {}: {}", input_dir, e))?;
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("srt"))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Runs the read/clean/write pipeline on every `.srt` file in a directory.
+///
+/// Output files keep the name of their input file. The output directory is created if it
+/// doesn't exist.
+///
+/// # Arguments
+///
+/// * `input_dir` - The directory containing the SRT files to clean.
+/// * `output_dir` - The directory to write the cleaned SRT files to.
+///
+/// # Returns
+///
+/// * `Result<usize, String>` - The number of files processed, or an error message on the first failure.
+pub fn process_dir(input_dir: &str, output_dir: &str) -> Result<usize, String> {
+    let paths = find_srt_files(input_dir)?;
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Unable to create directory {}: {}", output_dir, e))?;
+
+    for input_path in &paths {
+        let output_path = output_path(input_path, output_dir);
+        let count = process_file(
+            &input_path.to_string_lossy(),
+            &output_path.to_string_lossy(),
+        )?;
+        tracing::info!("Wrote {} subtitles to {}", count, output_path.display());
+    }
+
+    Ok(paths.len())
+}
+
+/// The outcome of processing a single file: its path and the number of subtitles written, or
+/// an error message.
+#[cfg(feature = "rayon")]
+pub type FileResult = (PathBuf, Result<usize, String>);
+
+/// Runs the read/clean/write pipeline on every `.srt` file in a directory, processing the
+/// files in parallel.
+///
+/// Unlike [`process_dir`], a failing file doesn't stop the others. Every file is read and
+/// written independently, so output files are never shared between threads.
+///
+/// # Arguments
+///
+/// * `input_dir` - The directory containing the SRT files to clean.
+/// * `output_dir` - The directory to write the cleaned SRT files to.
+///
+/// # Returns
+///
+/// * `Result<Vec<FileResult>, String>` - The outcome for each input file, or an error message
+///   if the directories can't be accessed.
+#[cfg(feature = "rayon")]
+pub fn process_dir_parallel(input_dir: &str, output_dir: &str) -> Result<Vec<FileResult>, String> {
+    use rayon::prelude::*;
+
+    let paths = find_srt_files(input_dir)?;
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Unable to create directory {}: {}", output_dir, e))?;
+
+    Ok(paths
+        .into_par_iter()
+        .map(|input_path| {
+            let output_path = output_path(&input_path, output_dir);
+            let result = process_file(
+                &input_path.to_string_lossy(),
+                &output_path.to_string_lossy(),
+            );
+            (input_path, result)
+        })
+        .collect())
+}
+
+/// Returns the path of the output file mirroring `input_path` in `output_dir`.
+fn output_path(input_path: &Path, output_dir: &str) -> PathBuf {
+    Path::new(output_dir).join(input_path.file_name().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_dir() {
+        let root =
+            std::env::temp_dir().join(format!("shu-2al_test_process_dir_{}", std::process::id()));
+        let input_dir = root.join("input");
+        let output_dir = root.join("output");
+        std::fs::create_dir_all(&input_dir).unwrap();
+
+        std::fs::write(
+            input_dir.join("first.srt"),
+            "1\n00:00:01,000 --> 00:00:02,000\nHello\n",
+        )
+        .unwrap();
+        std::fs::write(
+            input_dir.join("second.SRT"),
+            "1\n00:00:01,000 --> 00:00:02,000\nWorld\n\n2\n00:00:03,000 --> 00:00:04,000\nموسيقى\n",
+        )
+        .unwrap();
+        std::fs::write(input_dir.join("notes.txt"), "Not a subtitle file").unwrap();
+
+        let count = process_dir(input_dir.to_str().unwrap(), output_dir.to_str().unwrap());
+        let first = std::fs::read_to_string(output_dir.join("first.srt"));
+        let second = std::fs::read_to_string(output_dir.join("second.SRT"));
+        let notes_exists = output_dir.join("notes.txt").exists();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(count, Ok(2));
+        assert_eq!(first.unwrap(), "1\n00:00:01,000 --> 00:00:02,000\nHello\n");
+        assert_eq!(second.unwrap(), "1\n00:00:01,000 --> 00:00:02,000\nWorld\n");
+        assert!(!notes_exists);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_process_dir_parallel() {
+        let root = std::env::temp_dir().join(format!(
+            "shu-2al_test_process_dir_parallel_{}",
+            std::process::id()
+        ));
+        let input_dir = root.join("input");
+        let output_dir = root.join("output");
+        std::fs::create_dir_all(&input_dir).unwrap();
+
+        let names: Vec<String> = (1..=8).map(|i| format!("{}.srt", i)).collect();
+        for name in &names {
+            std::fs::write(
+                input_dir.join(name),
+                "1\n00:00:01,000 --> 00:00:02,000\nHello\n",
+            )
+            .unwrap();
+        }
+
+        let results =
+            process_dir_parallel(input_dir.to_str().unwrap(), output_dir.to_str().unwrap());
+        let outputs: Vec<bool> = names
+            .iter()
+            .map(|name| output_dir.join(name).exists())
+            .collect();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let results = results.unwrap();
+        assert_eq!(results.len(), names.len());
+        assert!(results.iter().all(|(_, result)| *result == Ok(1)));
+        assert!(outputs.into_iter().all(|exists| exists));
+    }
+
+    #[test]
+    fn test_process_dir_missing_input() {
+        let output_dir = std::env::temp_dir().join(format!(
+            "shu-2al_test_process_dir_missing_input_{}",
+            std::process::id()
+        ));
+        // Clear any directory left behind by a previous run
+        let _ = std::fs::remove_dir_all(&output_dir);
+
+        assert!(process_dir("/nonexistent/shu-2al", output_dir.to_str().unwrap()).is_err());
+        assert!(!output_dir.exists());
+    }
+}
@@ -0,0 +1,444 @@
+use std::{fmt, path::Path, time::Duration};
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use shu_2al::{
+    batch,
+    core::{
+        direction::Direction,
+        options::ClampMode,
+        srt::{SrtStats, SRT},
+        subtitle::Subtitle,
+        timestamp::parse_duration,
+    },
+};
+
+/// Cleans, shifts and converts SRT subtitle files.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Print a summary of what would change instead of writing the output.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// The operation to run on the input file.
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum Command {
+    /// Drop invalid subtitles and renumber the rest.
+    ///
+    /// If the input is a directory, every `.srt` file in it is cleaned into the output
+    /// directory.
+    Clean {
+        /// The SRT file (or directory of SRT files) to clean.
+        input: String,
+        /// The file (or directory) to write the cleaned subtitles to.
+        output: String,
+    },
+    /// Move every subtitle by an offset.
+    Shift {
+        /// The offset, e.g. `1500ms`, `2s`, `1m` or `00:00:02,000` (milliseconds if no unit is
+        /// given). Negative values move the subtitles backward.
+        #[arg(allow_hyphen_values = true, value_parser = parse_offset)]
+        delta: i64,
+        /// The SRT file to shift.
+        input: String,
+        /// The file to write the shifted subtitles to.
+        output: String,
+    },
+    /// Write the subtitles in another format.
+    Convert {
+        /// The format to convert to.
+        #[arg(long, value_enum)]
+        to: Format,
+        /// The SRT file to convert.
+        input: String,
+        /// The file to write the converted subtitles to.
+        output: String,
+    },
+    /// Print summary statistics of the subtitles.
+    Stats {
+        /// The SRT file to inspect.
+        input: String,
+        /// The file to write the statistics to, printed to stdout if omitted.
+        output: Option<String>,
+    },
+}
+
+/// Output formats supported by the `convert` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// SubRip (`.srt`).
+    Srt,
+    /// WebVTT (`.vtt`).
+    Vtt,
+}
+
+/// What a command changes in a file, as reported by `--dry-run`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Number of subtitle blocks read from the file.
+    pub read: usize,
+    /// Number of blocks dropped because they were malformed or invalid.
+    pub dropped: usize,
+    /// Number of kept subtitles whose index, timing or text changed.
+    pub modified: usize,
+}
+
+impl Summary {
+    /// Compares the subtitles as read with the processed ones, which are renumbered on write.
+    ///
+    /// # Arguments
+    ///
+    /// * `srt` - The processed SRT.
+    /// * `original` - The subtitles as they were read, before processing.
+    fn new(srt: &SRT, original: &[Subtitle]) -> Summary {
+        let modified = original
+            .iter()
+            .zip(&srt.subtitles)
+            .enumerate()
+            .filter(|(i, (before, after))| {
+                before.index != i + 1
+                    || before.start_time != after.start_time
+                    || before.end_time != after.end_time
+                    || before.text != after.text
+            })
+            .count();
+
+        Summary {
+            read: srt.subtitles.len() + srt.dropped(),
+            dropped: srt.dropped(),
+            modified,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "cues read: {}", self.read)?;
+        writeln!(f, "cues dropped: {}", self.dropped)?;
+        writeln!(f, "cues modified: {}", self.modified)
+    }
+}
+
+/// Runs the subcommand given on the command line.
+///
+/// # Arguments
+///
+/// * `cli` - The parsed command line.
+///
+/// # Returns
+///
+/// * `Result<(), String>` - Returns `Ok(())` if successful, or an error message if it fails.
+pub fn run(cli: &Cli) -> Result<(), String> {
+    if cli.dry_run {
+        print!("{}", dry_run(&cli.command)?);
+        return Ok(());
+    }
+
+    match &cli.command {
+        Command::Clean { input, output } => {
+            if Path::new(input).is_dir() {
+                return clean_dir(input, output);
+            }
+            let count = batch::process_file(input, output)?;
+            tracing::info!("File written successfully with {} subtitles!", count);
+        }
+        Command::Shift {
+            delta,
+            input,
+            output,
+        } => {
+            let mut srt = read(input)?;
+            shift(&mut srt, *delta)?;
+            let count = srt.write_file(output)?;
+            tracing::info!("File written successfully with {} subtitles!", count);
+        }
+        Command::Convert { to, input, output } => {
+            let srt = read(input)?;
+            let count = match to {
+                Format::Srt => srt.write_file(output)?,
+                Format::Vtt => srt.write_vtt_file(output)?,
+            };
+            tracing::info!("File written successfully with {} subtitles!", count);
+        }
+        Command::Stats { input, output } => {
+            let report = format_stats(&read(input)?.stats());
+            match output {
+                Some(output) => std::fs::write(output, report).map_err(|e| e.to_string())?,
+                None => print!("{}", report),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Cleans every `.srt` file in a directory, see [`batch::process_dir`].
+#[cfg(not(feature = "rayon"))]
+fn clean_dir(input: &str, output: &str) -> Result<(), String> {
+    let count = batch::process_dir(input, output)?;
+    tracing::info!("Processed {} files successfully!", count);
+    Ok(())
+}
+
+/// Cleans every `.srt` file in a directory in parallel, see [`batch::process_dir_parallel`].
+///
+/// Each failing file is reported to stderr without stopping the others.
+///
+/// # Returns
+///
+/// * `Result<(), String>` - Returns `Ok(())` if every file was cleaned, or an error message
+///   with the number of files that failed.
+#[cfg(feature = "rayon")]
+fn clean_dir(input: &str, output: &str) -> Result<(), String> {
+    let results = batch::process_dir_parallel(input, output)?;
+    let mut failed = 0;
+    for (path, result) in &results {
+        match result {
+            Ok(count) => tracing::info!("Wrote {} subtitles from {}", count, path.display()),
+            Err(e) => {
+                eprintln!("{}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} files failed", failed, results.len()));
+    }
+    tracing::info!("Processed {} files successfully!", results.len());
+    Ok(())
+}
+
+/// Processes the input of a command without writing anything.
+///
+/// # Arguments
+///
+/// * `command` - The subcommand to preview.
+///
+/// # Returns
+///
+/// * `Result<String, String>` - The summary of the changes, with one section per file when the
+///   input is a directory, or an error message if it fails.
+fn dry_run(command: &Command) -> Result<String, String> {
+    let (input, delta) = match command {
+        Command::Shift { delta, input, .. } => (input, *delta),
+        Command::Clean { input, .. }
+        | Command::Convert { input, .. }
+        | Command::Stats { input, .. } => (input, 0),
+    };
+
+    let summarize = |path: &str| -> Result<Summary, String> {
+        let mut srt = read(path)?;
+        let original = srt.subtitles.clone();
+        shift(&mut srt, delta)?;
+        Ok(Summary::new(&srt, &original))
+    };
+
+    if matches!(command, Command::Clean { .. }) && Path::new(input).is_dir() {
+        let mut report = String::new();
+        for path in batch::find_srt_files(input)? {
+            let path = path.to_string_lossy();
+            report.push_str(&format!("{}:\n{}", path, summarize(&path)?));
+        }
+        return Ok(report);
+    }
+    Ok(summarize(input)?.to_string())
+}
+
+/// Parses a signed offset for the `shift` subcommand, see [`parse_duration`].
+///
+/// # Returns
+///
+/// * `Result<i64, String>` - The offset in milliseconds, or an error message if it is malformed.
+fn parse_offset(input: &str) -> Result<i64, String> {
+    let (sign, duration) = match input.strip_prefix('-') {
+        Some(duration) => (-1, duration),
+        None => (1, input),
+    };
+    let millis = i64::try_from(parse_duration(duration)?.as_millis())
+        .map_err(|_| format!("Offset \"{}\" is too large", input))?;
+    Ok(sign * millis)
+}
+
+/// Moves every subtitle by a signed number of milliseconds.
+fn shift(srt: &mut SRT, delta: i64) -> Result<(), String> {
+    let offset = Duration::from_millis(delta.unsigned_abs());
+    srt.shift_all(offset, Direction::from_sign(delta), ClampMode::Saturate)
+        .map_err(|e| e.to_string())
+}
+
+/// Reads an SRT file, turning read errors into messages that name the file.
+fn read(input: &str) -> Result<SRT, String> {
+    let mut srt = SRT::new(input);
+    srt.read_file().map_err(|e| format!("{}: {}", input, e))?;
+    Ok(srt)
+}
+
+/// Formats statistics as one `name: value` line per field.
+fn format_stats(stats: &SrtStats) -> String {
+    format!(
+        "cues: {}\non screen: {:?}\ngaps: {:?}\nlongest: {:?}\nshortest: {:?}\naverage cps: {:.2}\n",
+        stats.cue_count,
+        stats.total_on_screen,
+        stats.total_gap,
+        stats.longest,
+        stats.shortest,
+        stats.average_cps
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_parse_subcommands() {
+        let cli = Cli::try_parse_from(["shu-2al", "clean", "in.srt", "out.srt"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Clean {
+                input: "in.srt".to_string(),
+                output: "out.srt".to_string(),
+            }
+        );
+
+        let cli = Cli::try_parse_from(["shu-2al", "shift", "-1500", "in.srt", "out.srt"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Shift {
+                delta: -1500,
+                input: "in.srt".to_string(),
+                output: "out.srt".to_string(),
+            }
+        );
+
+        let cli = Cli::try_parse_from(["shu-2al", "convert", "--to", "vtt", "in.srt", "out.vtt"])
+            .unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Convert {
+                to: Format::Vtt,
+                input: "in.srt".to_string(),
+                output: "out.vtt".to_string(),
+            }
+        );
+
+        let cli = Cli::try_parse_from(["shu-2al", "stats", "in.srt"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Stats {
+                input: "in.srt".to_string(),
+                output: None,
+            }
+        );
+        assert!(!cli.dry_run);
+
+        let cli =
+            Cli::try_parse_from(["shu-2al", "clean", "in.srt", "out.srt", "--dry-run"]).unwrap();
+        assert!(cli.dry_run);
+    }
+
+    #[test]
+    fn test_cli_parse_shift_units() {
+        for (delta, millis) in [("2s", 2000), ("-1500ms", -1500), ("-00:00:01,250", -1250)] {
+            let cli =
+                Cli::try_parse_from(["shu-2al", "shift", delta, "in.srt", "out.srt"]).unwrap();
+            assert!(matches!(cli.command, Command::Shift { delta, .. } if delta == millis));
+        }
+        assert!(Cli::try_parse_from(["shu-2al", "shift", "2h", "in.srt", "out.srt"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_invalid() {
+        assert!(Cli::try_parse_from(["shu-2al"]).is_err());
+        assert!(Cli::try_parse_from(["shu-2al", "shift", "in.srt", "out.srt"]).is_err());
+        assert!(
+            Cli::try_parse_from(["shu-2al", "convert", "--to", "ass", "in.srt", "out"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_run_shift() {
+        let input = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/test_3/input.srt");
+        let output = std::env::temp_dir().join(format!(
+            "shu-2al_test_cli_run_shift_{}.srt",
+            std::process::id()
+        ));
+        let command = Command::Shift {
+            delta: 1000,
+            input: input.to_string(),
+            output: output.to_str().unwrap().to_string(),
+        };
+
+        run(&Cli {
+            dry_run: false,
+            command,
+        })
+        .unwrap();
+
+        let original = read(input).unwrap();
+        let shifted = read(output.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        assert_eq!(shifted.subtitles.len(), original.subtitles.len());
+        assert_eq!(
+            shifted.subtitles[0].start_time.to_millis(),
+            original.subtitles[0].start_time.to_millis() + 1000
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_cli_run_clean_dir_parallel() {
+        let root = std::env::temp_dir().join(format!(
+            "shu-2al_test_cli_run_clean_dir_parallel_{}",
+            std::process::id()
+        ));
+        let input_dir = root.join("input");
+        let output_dir = root.join("output");
+        std::fs::create_dir_all(&input_dir).unwrap();
+        for name in ["good.srt", "bad.srt"] {
+            std::fs::write(
+                input_dir.join(name),
+                "1\n00:00:01,000 --> 00:00:02,000\nHello\n",
+            )
+            .unwrap();
+        }
+        // A directory in place of the output file makes writing it fail.
+        std::fs::create_dir_all(output_dir.join("bad.srt")).unwrap();
+
+        let command = Command::Clean {
+            input: input_dir.to_str().unwrap().to_string(),
+            output: output_dir.to_str().unwrap().to_string(),
+        };
+        let result = run(&Cli {
+            dry_run: false,
+            command,
+        });
+        let good = std::fs::read_to_string(output_dir.join("good.srt"));
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(result, Err("1 of 2 files failed".to_string()));
+        assert_eq!(good.unwrap(), "1\n00:00:01,000 --> 00:00:02,000\nHello\n");
+    }
+
+    #[test]
+    fn test_cli_dry_run_summary() {
+        let input = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parse_errors/broken_block.srt"
+        );
+        let output = std::env::temp_dir().join("shu-2al_test_cli_dry_run_summary.srt");
+        let command = Command::Clean {
+            input: input.to_string(),
+            output: output.to_str().unwrap().to_string(),
+        };
+
+        let summary = dry_run(&command).unwrap();
+
+        assert_eq!(summary, "cues read: 4\ncues dropped: 1\ncues modified: 1\n");
+        assert!(!output.exists());
+    }
+}
@@ -0,0 +1,316 @@
+use std::{fmt, iter::Peekable};
+
+use super::options::BlankLineMode;
+
+/// An error produced while grouping lines into blocks.
+#[derive(Debug, PartialEq)]
+pub enum BlockError<E> {
+    /// Reading a line failed.
+    Line(E),
+    /// A block grew past the maximum number of lines. The lines up to the next blank line
+    /// are skipped.
+    TooLarge {
+        /// The 1-based number of the first line of the block.
+        start_line: usize,
+        /// The maximum number of lines of a block.
+        max_lines: usize,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for BlockError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockError::Line(e) => write!(f, "{}", e),
+            BlockError::TooLarge {
+                start_line,
+                max_lines,
+            } => write!(
+                f,
+                "Block starting at line {} exceeds {} lines",
+                start_line, max_lines
+            ),
+        }
+    }
+}
+
+/// Groups the lines of an SRT file into subtitle blocks.
+///
+/// Blocks are separated by blank lines, see [`BlankLineMode`]. A block that starts right
+/// after another one without a blank separator (an index line followed by a timestamp line)
+/// is also detected, and its index line is carried over to it.
+///
+/// A byte order mark at the start of the input is removed. Lines are trimmed, including stray
+/// `\r` characters, unless trimming is disabled with [`Blocks::with_trim`], in which case only
+/// the latter are removed. Errors from the underlying lines are passed through.
+pub struct Blocks<I: Iterator> {
+    lines: Peekable<I>,
+    /// How blank lines are handled.
+    mode: BlankLineMode,
+    /// The maximum number of lines of a block, if any.
+    max_lines: Option<usize>,
+    /// Whether surrounding whitespace is trimmed from the lines.
+    trim: bool,
+    /// The number of lines read so far.
+    line_number: usize,
+    /// Lines of the next block, read while looking for the end of the current one.
+    carry: Vec<String>,
+    /// The line number of the first line in `carry`.
+    carry_start: usize,
+}
+
+impl<I: Iterator> Blocks<I> {
+    /// Creates a new `Blocks` iterator over the given lines, where every blank line ends a
+    /// block.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines of the file, without their line terminators.
+    pub fn new(lines: I) -> Self {
+        Blocks::with_mode(lines, BlankLineMode::default())
+    }
+
+    /// Creates a new `Blocks` iterator over the given lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines of the file, without their line terminators.
+    /// * `mode` - How blank lines are handled.
+    pub fn with_mode(lines: I, mode: BlankLineMode) -> Self {
+        Blocks {
+            lines: lines.peekable(),
+            mode,
+            max_lines: None,
+            trim: true,
+            line_number: 0,
+            carry: Vec::new(),
+            carry_start: 0,
+        }
+    }
+
+    /// Limits the number of lines of a block. Larger blocks are reported as
+    /// [`BlockError::TooLarge`] instead of being read into memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_lines` - The maximum number of lines of a block.
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Sets whether surrounding whitespace is trimmed from the lines (default: `true`). Lines
+    /// containing only whitespace end a block either way.
+    ///
+    /// # Arguments
+    ///
+    /// * `trim` - Whether the lines are trimmed.
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+}
+
+impl<I, E> Blocks<I>
+where
+    I: Iterator<Item = Result<String, E>>,
+{
+    /// Checks whether a line following a blank line starts a new block, looking at the line
+    /// after it if needed.
+    fn starts_block(&mut self, line: &str) -> bool {
+        line.contains("-->")
+            || (line.trim().parse::<usize>().is_ok()
+                && matches!(self.lines.peek(), Some(Ok(next)) if next.contains("-->")))
+    }
+
+    /// Skips the lines up to and including the next blank line.
+    fn skip_block(&mut self) {
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            if line.is_ok_and(|line| line.trim().is_empty()) {
+                break;
+            }
+        }
+    }
+}
+
+impl<I, E> Iterator for Blocks<I>
+where
+    I: Iterator<Item = Result<String, E>>,
+{
+    type Item = Result<Vec<String>, BlockError<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block = std::mem::take(&mut self.carry);
+        let mut start_line = self.carry_start;
+        // Blank lines read since the last line of the block, only used in `BeforeIndex` mode
+        let mut blank_lines = 0;
+        while let Some(line) = self.lines.next() {
+            self.line_number += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(BlockError::Line(e))),
+            };
+            // Readers yielding raw UTF-8 lines keep the byte order mark at the start of the input
+            let line = match self.line_number {
+                1 => line.strip_prefix('\u{feff}').unwrap_or(&line),
+                _ => &line,
+            };
+            // `lines` only strips the `\r` of a `\r\n` terminator, trim stray ones as well
+            let line = line.trim_end_matches('\r');
+            let line = if self.trim { line.trim() } else { line };
+            if line.trim().is_empty() {
+                if block.is_empty() {
+                    continue;
+                }
+                match self.mode {
+                    BlankLineMode::Separator => return Some(Ok(block)),
+                    BlankLineMode::BeforeIndex => {
+                        blank_lines += 1;
+                        continue;
+                    }
+                }
+            }
+
+            if blank_lines > 0 {
+                if self.starts_block(line) {
+                    self.carry.push(line.to_string());
+                    self.carry_start = self.line_number;
+                    return Some(Ok(block));
+                }
+                block.extend(std::iter::repeat_n(String::new(), blank_lines));
+                blank_lines = 0;
+            }
+
+            if line.contains("-->") && block.iter().any(|l| l.contains("-->")) {
+                // A new subtitle started without a blank separator, carry its index over
+                self.carry = block
+                    .pop_if(|l| l.trim().parse::<usize>().is_ok())
+                    .into_iter()
+                    .collect();
+                self.carry_start = self.line_number - self.carry.len();
+                self.carry.push(line.to_string());
+                return Some(Ok(block));
+            }
+
+            if block.is_empty() {
+                start_line = self.line_number;
+            }
+            block.push(line.to_string());
+
+            if let Some(max_lines) = self.max_lines.filter(|&max| block.len() > max) {
+                self.skip_block();
+                return Some(Err(BlockError::TooLarge {
+                    start_line,
+                    max_lines,
+                }));
+            }
+        }
+
+        // The last block of the file may not be followed by a blank line
+        (!block.is_empty()).then_some(Ok(block))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+
+    fn blocks(content: &str, mode: BlankLineMode) -> Vec<Vec<String>> {
+        let lines = content.lines().map(|l| Ok::<_, Infallible>(l.to_string()));
+        Blocks::with_mode(lines, mode)
+            .map(|block| block.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_blocks() {
+        let content = "\n1\r\n00:00:01,000 --> 00:00:02,000\r\nFirst\r\n\r\n\r\n\
+                       2\n00:00:03,000 --> 00:00:04,000\nSecond\n\
+                       3\n00:00:05,000 --> 00:00:06,000\nThird";
+
+        assert_eq!(
+            blocks(content, BlankLineMode::Separator),
+            vec![
+                vec!["1", "00:00:01,000 --> 00:00:02,000", "First"],
+                vec!["2", "00:00:03,000 --> 00:00:04,000", "Second"],
+                vec!["3", "00:00:05,000 --> 00:00:06,000", "Third"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_max_lines() {
+        let content = "1\n00:00:01,000 --> 00:00:02,000\nFirst\n\n\
+                       2\n00:00:03,000 --> 00:00:04,000\nOne\nTwo\nThree\n\n\
+                       3\n00:00:05,000 --> 00:00:06,000\nThird";
+        let lines = content.lines().map(|l| Ok::<_, Infallible>(l.to_string()));
+
+        let blocks: Vec<_> = Blocks::new(lines).with_max_lines(4).collect();
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(
+            blocks[1],
+            Err(BlockError::TooLarge {
+                start_line: 5,
+                max_lines: 4
+            })
+        );
+        assert_eq!(
+            blocks[2].as_ref().unwrap(),
+            &vec!["3", "00:00:05,000 --> 00:00:06,000", "Third"]
+        );
+    }
+
+    #[test]
+    fn test_blocks_without_trim() {
+        let content = " 1 \n00:00:01,000 --> 00:00:02,000\n  Indented\n \n\
+                       2\n00:00:03,000 --> 00:00:04,000\nSecond  ";
+        let lines = content.lines().map(|l| Ok::<_, Infallible>(l.to_string()));
+
+        let blocks: Vec<_> = Blocks::new(lines)
+            .with_trim(false)
+            .map(|block| block.unwrap())
+            .collect();
+
+        assert_eq!(
+            blocks,
+            vec![
+                vec![" 1 ", "00:00:01,000 --> 00:00:02,000", "  Indented"],
+                vec!["2", "00:00:03,000 --> 00:00:04,000", "Second  "],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_empty() {
+        assert!(blocks("", BlankLineMode::Separator).is_empty());
+        assert!(blocks("\n\r\n  \n", BlankLineMode::Separator).is_empty());
+        assert!(blocks("\n\r\n  \n", BlankLineMode::BeforeIndex).is_empty());
+    }
+
+    #[test]
+    fn test_blocks_before_index() {
+        let content = "1\n00:00:01,000 --> 00:00:02,000\nFirst\n\n\n42\nstill first\n\n\
+                       2\n00:00:03,000 --> 00:00:04,000\nSecond\n\n\
+                       00:00:05,000 --> 00:00:06,000\nThird\n\n";
+
+        assert_eq!(
+            blocks(content, BlankLineMode::BeforeIndex),
+            vec![
+                vec![
+                    "1",
+                    "00:00:01,000 --> 00:00:02,000",
+                    "First",
+                    "",
+                    "",
+                    "42",
+                    "still first"
+                ],
+                vec!["2", "00:00:03,000 --> 00:00:04,000", "Second"],
+                vec!["00:00:05,000 --> 00:00:06,000", "Third"],
+            ]
+        );
+    }
+}
@@ -1,8 +1,66 @@
 /// This module defines the `Direction` enum, which represents the direction
 /// in which we move timestamps in a subtitle file.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum Direction {
     #[default]
     Forward,
     Backward,
 }
+
+impl Direction {
+    /// Returns the direction of a signed offset. Zero is forward.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The signed offset.
+    pub fn from_sign(value: i64) -> Self {
+        if value < 0 {
+            Direction::Backward
+        } else {
+            Direction::Forward
+        }
+    }
+
+    /// Returns the sign of the direction: `1` forward, `-1` backward.
+    pub fn sign(&self) -> i64 {
+        match self {
+            Direction::Forward => 1,
+            Direction::Backward => -1,
+        }
+    }
+
+    /// Applies the direction to a number of milliseconds, negating it when moving backward.
+    ///
+    /// # Arguments
+    ///
+    /// * `millis` - The unsigned amount, in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// * `i64` - The signed offset in milliseconds.
+    pub fn apply(&self, millis: i64) -> i64 {
+        self.sign() * millis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction_sign() {
+        assert_eq!(Direction::Forward.sign(), 1);
+        assert_eq!(Direction::Backward.sign(), -1);
+
+        assert_eq!(Direction::from_sign(1500), Direction::Forward);
+        assert_eq!(Direction::from_sign(0), Direction::Forward);
+        assert_eq!(Direction::from_sign(-1500), Direction::Backward);
+    }
+
+    #[test]
+    fn test_direction_apply() {
+        assert_eq!(Direction::Forward.apply(1500), 1500);
+        assert_eq!(Direction::Backward.apply(1500), -1500);
+        assert_eq!(Direction::from_sign(-250).apply(250), -250);
+    }
+}
@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use encoding_rs::{Encoding, WINDOWS_1256};
+
+use super::error::SRTError;
+
+/// Encoding assumed for files that are neither marked with a BOM nor valid UTF-8.
+///
+/// Legacy Arabic subtitles are most commonly authored in Windows-1256.
+pub const FALLBACK_ENCODING: &Encoding = WINDOWS_1256;
+
+/// Decodes the raw content of a subtitle file into UTF-8.
+///
+/// The encoding is detected as follows:
+/// 1. A leading byte order mark (UTF-8, UTF-16LE or UTF-16BE) selects its encoding and is stripped.
+/// 2. Content that is valid UTF-8 is used as is.
+/// 3. Anything else is decoded with [`FALLBACK_ENCODING`].
+///
+/// # Arguments
+///
+/// * `bytes` - The raw content of the file.
+///
+/// # Returns
+///
+/// * `Cow<str>` - The decoded content.
+pub fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+        return encoding.decode_without_bom_handling(&bytes[bom_length..]).0;
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(content) => Cow::Borrowed(content),
+        Err(_) => FALLBACK_ENCODING.decode_without_bom_handling(bytes).0,
+    }
+}
+
+/// Decodes the raw content of a subtitle file using an explicit encoding.
+/// A leading byte order mark is stripped, but does not override the given encoding.
+///
+/// # Arguments
+///
+/// * `bytes` - The raw content of the file.
+/// * `label` - The label of the encoding, e.g. `"windows-1256"` or `"utf-8"`.
+///
+/// # Returns
+///
+/// * `Result<Cow<str>, SRTError>` - The decoded content, or an error if the label is unknown.
+pub fn decode_with_label<'a>(bytes: &'a [u8], label: &str) -> Result<Cow<'a, str>, SRTError> {
+    let encoding = Encoding::for_label(label.as_bytes())
+        .ok_or_else(|| SRTError::InvalidInput(format!("Unknown encoding: {}", label)))?;
+
+    let bytes = match Encoding::for_bom(bytes) {
+        Some((_, bom_length)) => &bytes[bom_length..],
+        None => bytes,
+    };
+    Ok(encoding.decode_without_bom_handling(bytes).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_utf8() {
+        assert_eq!(decode("مرحبا".as_bytes()), "مرحبا");
+    }
+
+    #[test]
+    fn test_decode_utf8_bom() {
+        assert_eq!(decode(b"\xEF\xBB\xBF1\n"), "1\n");
+    }
+
+    #[test]
+    fn test_decode_windows_1256() {
+        // "مرحبا" in Windows-1256
+        assert_eq!(decode(b"\xE3\xD1\xCD\xC8\xC7"), "مرحبا");
+    }
+
+    #[test]
+    fn test_decode_with_label() {
+        assert_eq!(
+            decode_with_label(b"caf\xE9", "windows-1252").unwrap(),
+            "café"
+        );
+        assert!(decode_with_label(b"", "not-an-encoding").is_err());
+    }
+}
@@ -1,6 +1,10 @@
+pub mod blocks;
 pub mod direction;
+pub mod encoding;
+pub mod error;
 pub mod language;
+pub mod options;
 pub mod srt;
 pub mod subtitle;
+pub mod text_direction;
 pub mod timestamp;
-pub mod error;
\ No newline at end of file
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+/// How malformed subtitle blocks are handled when reading a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Abort on the first malformed block with an error.
+    Strict,
+    /// Skip malformed blocks, recording a warning for each of them.
+    #[default]
+    Lenient,
+}
+
+/// How blank lines split the content of a file into subtitle blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlankLineMode {
+    /// Every blank line ends the current block.
+    #[default]
+    Separator,
+    /// A blank line only ends the current block if the next line starts a new one, i.e. it is
+    /// a timestamp line or an index line followed by a timestamp line. Other blank lines are
+    /// kept in the text of the subtitle.
+    ///
+    /// This preserves blank lines inside the text of hand-edited files, but a block that is
+    /// missing its timestamp line is merged into the previous subtitle instead of being
+    /// reported as malformed.
+    BeforeIndex,
+}
+
+/// Options controlling how subtitles are read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// How malformed blocks are handled (default: `Lenient`).
+    pub mode: ParseMode,
+    /// Whether to sort the subtitles chronologically after reading them if they are out of
+    /// order (default: `false`).
+    pub auto_sort: bool,
+    /// Whether timestamps may use a `.` instead of a `,` before the milliseconds, as in
+    /// WebVTT (default: `false`).
+    pub lenient_timestamps: bool,
+    /// How blank lines split the file into blocks (default: `Separator`).
+    pub blank_lines: BlankLineMode,
+    /// Rules deciding which subtitles are dropped as invalid while reading.
+    pub validation: ValidationOptions,
+    /// The maximum number of lines of a block, guarding against files without blank
+    /// separators (default: `100`).
+    pub max_block_lines: usize,
+    /// Whether leading and trailing whitespace is trimmed from every line of text (default:
+    /// `true`). Disable it to keep intentional indentation.
+    pub trim_text: bool,
+    /// The minimum duration of a subtitle, or `None` to allow any duration, including zero
+    /// (default: `None`). Some players drop zero-length subtitles.
+    pub min_duration: Option<Duration>,
+    /// Whether subtitles shorter than `min_duration` are extended to it instead of being
+    /// rejected as malformed (default: `false`).
+    pub extend_short_cues: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            mode: ParseMode::default(),
+            auto_sort: false,
+            lenient_timestamps: false,
+            blank_lines: BlankLineMode::default(),
+            validation: ValidationOptions::default(),
+            max_block_lines: 100,
+            trim_text: true,
+            min_duration: None,
+            extend_short_cues: false,
+        }
+    }
+}
+
+/// Options controlling which subtitles are considered valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Whether subtitles made up of punctuation and whitespace only are rejected (default:
+    /// `true`).
+    pub reject_punctuation_only: bool,
+    /// Whether banned phrases are matched ignoring diacritics, such as the Arabic harakat, and
+    /// Unicode compatibility variants, such as presentation forms (default: `false`).
+    pub ignore_diacritics: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            reject_punctuation_only: true,
+            ignore_diacritics: false,
+        }
+    }
+}
+
+/// How timestamps moved before `00:00:00,000` are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClampMode {
+    /// Silently clamp the timestamps to `00:00:00,000`.
+    #[default]
+    Saturate,
+    /// Fail with an error naming the first subtitle that would be clamped.
+    Error,
+}
+
+/// Line terminator used when writing subtitle files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Unix line endings (`\n`).
+    #[default]
+    Lf,
+    /// Windows line endings (`\r\n`).
+    Crlf,
+}
+
+impl LineEnding {
+    /// Returns the line terminator as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Options controlling how subtitles are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Line terminator emitted after every line (default: `Lf`).
+    pub line_ending: LineEnding,
+    /// Whether each block starts with its numeric index line (default: `true`).
+    pub indices: bool,
+    /// Whether blocks are separated by a blank line (default: `true`).
+    pub blank_separator: bool,
+    /// Whether the subtitles are validated before writing, failing the write if they are
+    /// inconsistent (default: `false`).
+    pub strict: bool,
+    /// Whether validation reports subtitles overlapping the next one (default: `false`).
+    pub reject_overlaps: bool,
+    /// The index of the first subtitle, e.g. `0` for legacy players (default: `1`).
+    pub index_start: usize,
+    /// The increment between the indices of consecutive subtitles (default: `1`).
+    pub index_step: usize,
+    /// Whether blank lines inside the text of a subtitle are written (default: `false`).
+    /// They end the subtitle when read back unless it is read in
+    /// [`BlankLineMode::BeforeIndex`] mode, so they are left out by default.
+    pub keep_blank_lines: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            line_ending: LineEnding::default(),
+            indices: true,
+            blank_separator: true,
+            strict: false,
+            reject_overlaps: false,
+            index_start: 1,
+            index_step: 1,
+            keep_blank_lines: false,
+        }
+    }
+}
+
+/// Options shared by the operations merging adjacent subtitles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeOptions {
+    /// The maximum gap between two subtitles for them to be merged, or `None` to merge them
+    /// regardless of the gap (default: `None`). [`SRT::merge_close`] treats `None` as a zero
+    /// gap, only merging subtitles that touch or overlap.
+    ///
+    /// [`SRT::merge_close`]: crate::core::srt::SRT::merge_close
+    pub max_gap: Option<Duration>,
+    /// The separator inserted between the texts of merged subtitles (default: `"\n"`).
+    pub join_separator: String,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        MergeOptions {
+            max_gap: None,
+            join_separator: "\n".to_string(),
+        }
+    }
+}
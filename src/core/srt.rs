@@ -1,14 +1,155 @@
 use std::{
+    convert::Infallible,
     fs::File,
-    io::{BufRead, BufReader, BufWriter, Write},
+    io::{BufRead, BufWriter, Write},
+    time::Duration,
 };
 
-use super::{error::SRTError, subtitle::Subtitle};
+use super::{
+    blocks::Blocks,
+    direction::Direction,
+    encoding,
+    error::SRTError,
+    options::{ClampMode, MergeOptions, ParseMode, ParseOptions, WriteOptions},
+    subtitle::Subtitle,
+    timestamp::Timestamp,
+};
+
+/// Summary statistics of an SRT file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SrtStats {
+    /// Number of subtitles in the file.
+    pub cue_count: usize,
+    /// Sum of the durations of all subtitles.
+    pub total_on_screen: Duration,
+    /// Sum of the gaps between consecutive subtitles.
+    pub total_gap: Duration,
+    /// Duration of the longest subtitle.
+    pub longest: Duration,
+    /// Duration of the shortest subtitle.
+    pub shortest: Duration,
+    /// Average characters per second (whitespace excluded) over the on-screen time.
+    pub average_cps: f64,
+}
+
+/// A difference between two versions of an SRT file, see [`SRT::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffEntry {
+    /// A subtitle only present in the new version.
+    Added(Subtitle),
+    /// A subtitle only present in the old version.
+    Removed(Subtitle),
+    /// A subtitle present in both versions, with different timing or text.
+    Modified {
+        /// The subtitle in the old version.
+        before: Subtitle,
+        /// The subtitle in the new version.
+        after: Subtitle,
+        /// Whether the start or end time changed.
+        time_changed: bool,
+        /// Whether the text changed.
+        text_changed: bool,
+    },
+}
+
+/// The fields of an ASS event line, used when the `[Events]` section has no `Format:` line.
+const ASS_EVENT_FORMAT: [&str; 10] = [
+    "Layer", "Start", "End", "Style", "Name", "MarginL", "MarginR", "MarginV", "Effect", "Text",
+];
+
+/// Parses the values of an ASS `Dialogue:` line into a subtitle.
+///
+/// Override tags such as `{\i1}` are removed, and the `\N` and `\n` line breaks and `\h`
+/// hard spaces are converted.
+///
+/// # Arguments
+///
+/// * `values` - The comma-separated values following `Dialogue:`.
+/// * `format` - The field names of the `Format:` line. `Text` must be the last field, since
+///   it may contain commas.
+///
+/// # Returns
+///
+/// * `Result<Subtitle, String>` - The subtitle, or an error message if the line is malformed.
+fn parse_ass_dialogue(values: &str, format: &[String]) -> Result<Subtitle, String> {
+    let values: Vec<&str> = values.splitn(format.len(), ',').map(str::trim).collect();
+    if values.len() != format.len() {
+        return Err("Missing dialogue fields".to_string());
+    }
+    let field = |name: &str| {
+        format
+            .iter()
+            .position(|field| field.eq_ignore_ascii_case(name))
+            .map(|i| values[i])
+            .ok_or(format!("Missing {} field", name))
+    };
+
+    let mut subtitle = Subtitle {
+        index: 0,
+        start_time: Timestamp::from_ass(field("Start")?)?,
+        end_time: Timestamp::from_ass(field("End")?)?,
+        text: field("Text")?.to_string(),
+        settings: None,
+    };
+    if subtitle.end_time < subtitle.start_time {
+        return Err("End time before start time".to_string());
+    }
+
+    subtitle.strip_tags();
+    subtitle.text = subtitle
+        .text
+        .replace("\\N", "\n")
+        .replace("\\n", "\n")
+        .replace("\\h", " ");
+    Ok(subtitle)
+}
+
+/// Feeds bytes into a 64-bit FNV-1a hash, which unlike the standard library hashers is
+/// guaranteed to be the same across runs and Rust versions.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Checks whether a line of text looks like a stray index or timestamp line rather than
+/// dialogue, see [`SRT::find_suspicious`].
+fn is_index_or_timestamp(line: &str) -> bool {
+    let line = line.trim();
+    let is_timestamp = |s: &str| Timestamp::from_string_with(s.trim(), true).is_ok();
+    (!line.is_empty() && line.chars().all(|c| c.is_ascii_digit()))
+        || is_timestamp(line)
+        || line
+            .split_once("-->")
+            .is_some_and(|(start, end)| is_timestamp(start) || is_timestamp(end))
+}
 
+/// Checks whether a path names a gzip-compressed file.
+#[cfg(feature = "gzip")]
+fn is_gzip_path(file_path: &str) -> bool {
+    std::path::Path::new(file_path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"))
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SRT {
     pub file_path: String,
     /// The list of subtitles in the SRT file.
     pub subtitles: Vec<Subtitle>,
+    /// Options used when reading the subtitles.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub parse_options: ParseOptions,
+    /// Options used when writing the subtitles.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub write_options: WriteOptions,
+    /// Warnings collected while reading the subtitles.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) warnings: Vec<String>,
+    /// Number of blocks dropped while reading the subtitles.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) dropped: usize,
 }
 
 impl SRT {
@@ -24,41 +165,321 @@ impl SRT {
     pub fn new(file_path: &str) -> Self {
         SRT {
             file_path: file_path.to_string(),
-            subtitles: Vec::new(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `SRT` instance from the raw content of an SRT file, e.g. received over
+    /// the network, with the default [`ParseOptions`]. The `file_path` is left empty.
+    ///
+    /// The encoding is detected as in [`SRT::read_file`], and a byte order mark is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The content of the SRT file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SRT, String>` - Returns the parsed `SRT` instance, or an error message if it fails.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SRT, String> {
+        let mut srt = SRT::default();
+        srt.parse(&encoding::decode(bytes))
+            .map_err(|e| e.to_string())?;
+        Ok(srt)
+    }
+
+    /// Creates a new `SRT` instance by concatenating the files of a movie split into parts,
+    /// e.g. `movie.part1.srt` and `movie.part2.srt`. The `file_path` is left empty.
+    ///
+    /// Each part is shifted by the cumulative length of the parts before it, where the length
+    /// of a part is taken as the end of its last subtitle. The subtitles are then renumbered.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The paths to the parts, in order.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SRT, String>` - Returns the concatenated `SRT` instance, or an error message naming the part that failed to read.
+    pub fn from_parts(paths: &[&str]) -> Result<SRT, String> {
+        let mut srt = SRT::default();
+        let mut offset = Duration::ZERO;
+        for path in paths {
+            let mut part = SRT::new(path);
+            part.read_file().map_err(|e| format!("{}: {}", path, e))?;
+            let length = part.subtitles.iter().map(|s| s.end_time.to_millis()).max();
+            srt.append(&part, offset);
+            offset += Duration::from_millis(length.unwrap_or(0));
         }
+        srt.renumber();
+        Ok(srt)
+    }
+
+    /// Returns the warnings collected while reading the subtitles, such as the malformed
+    /// blocks skipped in [`ParseMode::Lenient`].
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Returns the number of blocks dropped while reading the subtitles, either because they
+    /// were malformed or because the subtitle was invalid.
+    pub fn dropped(&self) -> usize {
+        self.dropped
     }
 
     /// Reads the SRT file and populates the `subtitles` vector.
     ///
+    /// Malformed blocks are handled according to `parse_options.mode`. If the subtitles are
+    /// out of order, a warning is recorded and they are sorted if `parse_options.auto_sort`
+    /// is set.
+    ///
+    /// The encoding of the file is detected automatically, see [`encoding::decode`]. With the
+    /// `gzip` feature, files ending in `.gz` are decompressed first.
+    ///
     /// # Returns
     ///
     /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error message if it fails.
     pub fn read_file(&mut self) -> Result<(), SRTError> {
-        let file = File::open(&self.file_path).map_err(|e| SRTError::FileError(e.to_string()))?;
-        let reader = BufReader::new(file);
-        let mut lines: Vec<String> = Vec::new();
-        for line in reader.lines() {
-            let line = line.map_err(|e| SRTError::SubtitleParseError(e.to_string()))?;
-            let line = line.trim().to_string();
-            if line.is_empty() {
-                lines.clear();
+        let bytes = self.read_bytes()?;
+        self.parse(&encoding::decode(&bytes))
+    }
+
+    /// Reads the SRT file using an explicit encoding and populates the `subtitles` vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The label of the encoding, e.g. `"windows-1256"` or `"utf-8"`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error message if it fails.
+    pub fn read_file_with_encoding(&mut self, label: &str) -> Result<(), SRTError> {
+        let bytes = self.read_bytes()?;
+        self.parse(&encoding::decode_with_label(&bytes, label)?)
+    }
+
+    /// Reads the dialogue of an ASS (SubStation Alpha) file at `file_path` and populates the
+    /// `subtitles` vector, discarding styling.
+    ///
+    /// Only the `Dialogue:` lines of the `[Events]` section are read, with their timestamps
+    /// converted from centiseconds. The subtitles are numbered in file order. Malformed lines
+    /// and invalid subtitles are handled as in [`SRT::read_file`].
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error with the number of the first malformed line.
+    pub fn read_ass(&mut self) -> Result<(), SRTError> {
+        let bytes = self.read_bytes()?;
+        self.parse_ass(&encoding::decode(&bytes))
+    }
+
+    /// Parses the decoded content of an ASS file, see [`SRT::read_ass`].
+    fn parse_ass(&mut self, content: &str) -> Result<(), SRTError> {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let mut format: Vec<String> = ASS_EVENT_FORMAT.iter().map(|f| f.to_string()).collect();
+        let mut in_events = false;
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                in_events = line.eq_ignore_ascii_case("[Events]");
+                continue;
+            }
+            if !in_events {
                 continue;
             }
 
-            lines.push(line);
+            if let Some(fields) = line.strip_prefix("Format:") {
+                format = fields.split(',').map(|f| f.trim().to_string()).collect();
+            } else if let Some(values) = line.strip_prefix("Dialogue:") {
+                let result = parse_ass_dialogue(values, &format);
+                self.push_parsed(result, &format!("line {}", i + 1))?;
+            }
+        }
+
+        self.check_order();
+        for (i, subtitle) in self.subtitles.iter_mut().enumerate() {
+            subtitle.index = i + 1;
+        }
+        Ok(())
+    }
 
-            if lines.len() > 1 {
-                if let Ok(subtitle) = Subtitle::new(&lines.iter().map(|e| e.as_str()).collect()) {
-                    self.subtitles.push(subtitle);
-                    lines.clear();
-                }
+    /// Reads the SRT file like [`SRT::read_file`], reporting the progress of the read, e.g.
+    /// for a progress bar.
+    ///
+    /// The file is read line by line, and `progress` is called with the number of bytes read
+    /// so far about every percent of the file length, and once more at the end with the full
+    /// length.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - The callback receiving the number of bytes read so far.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error message if it fails.
+    pub fn read_file_with_progress(
+        &mut self,
+        mut progress: impl FnMut(u64),
+    ) -> Result<(), SRTError> {
+        let file = File::open(&self.file_path).map_err(|e| SRTError::FileError(e.to_string()))?;
+        let length = file
+            .metadata()
+            .map_err(|e| SRTError::FileError(e.to_string()))?
+            .len();
+        let interval = (length / 100).max(1) as usize;
+
+        let mut reader = std::io::BufReader::new(file);
+        let mut bytes = Vec::with_capacity(length as usize);
+        let mut reported = 0;
+        while reader
+            .read_until(b'\n', &mut bytes)
+            .map_err(|e| SRTError::FileError(e.to_string()))?
+            > 0
+        {
+            if bytes.len() - reported >= interval {
+                progress(bytes.len() as u64);
+                reported = bytes.len();
+            }
+        }
+        progress(bytes.len() as u64);
+
+        let bytes = self.decompress(bytes)?;
+        self.parse(&encoding::decode(&bytes))
+    }
+
+    /// Reads the raw content of the SRT file, decompressing `.gz` files with the `gzip` feature.
+    fn read_bytes(&self) -> Result<Vec<u8>, SRTError> {
+        let bytes =
+            std::fs::read(&self.file_path).map_err(|e| SRTError::FileError(e.to_string()))?;
+        self.decompress(bytes)
+    }
+
+    /// Decompresses the raw content of `.gz` files with the `gzip` feature, and returns the
+    /// content of other files as is.
+    fn decompress(&self, bytes: Vec<u8>) -> Result<Vec<u8>, SRTError> {
+        #[cfg(feature = "gzip")]
+        if is_gzip_path(&self.file_path) {
+            use std::io::Read;
+
+            let mut decompressed = Vec::new();
+            flate2::read::GzDecoder::new(bytes.as_slice())
+                .read_to_end(&mut decompressed)
+                .map_err(|e| SRTError::FileError(e.to_string()))?;
+            return Ok(decompressed);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Lazily parses subtitles from a reader, one block at a time.
+    ///
+    /// Blocks are split as in [`SRT::read_file`] with the default [`ParseOptions`], but the
+    /// reader must be UTF-8 and the text of the subtitles is not validated, see
+    /// [`Subtitle::parse`].
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to read the SRT content from.
+    ///
+    /// # Returns
+    ///
+    /// * `impl Iterator<Item = Result<Subtitle, String>>` - The subtitles in file order, or an
+    ///   error message for each malformed block and read failure.
+    pub fn parse_iter<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Subtitle, String>> {
+        let max_lines = ParseOptions::default().max_block_lines;
+        Blocks::new(reader.lines())
+            .with_max_lines(max_lines)
+            .enumerate()
+            .map(|(i, block)| {
+                let block = block.map_err(|e| e.to_string())?;
+                let lines: Vec<&str> = block.iter().map(String::as_str).collect();
+                Subtitle::parse(&lines).map_err(|e| format!("block {}: {}", i + 1, e))
+            })
+    }
+
+    /// Parses the decoded content of an SRT file and populates the `subtitles` vector.
+    ///
+    /// The content is split into blocks by [`Blocks`]. Subtitles that are well-formed but
+    /// invalid (see [`Subtitle::is_valid`]) are dropped.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error with the number of the first malformed block.
+    ///   A block larger than `parse_options.max_block_lines` is an error in every mode.
+    fn parse(&mut self, content: &str) -> Result<(), SRTError> {
+        let lines = content
+            .lines()
+            .map(|line| Ok::<_, Infallible>(line.to_string()));
+        let blocks = Blocks::with_mode(lines, self.parse_options.blank_lines)
+            .with_max_lines(self.parse_options.max_block_lines)
+            .with_trim(self.parse_options.trim_text);
+        for (i, block) in blocks.enumerate() {
+            let block = block.map_err(|e| SRTError::SubtitleParseError(e.to_string()))?;
+            self.parse_block(&block, i + 1)?;
+        }
+
+        self.check_order();
+        Ok(())
+    }
+
+    /// Records a warning if the subtitles read are out of order, sorting them if
+    /// `parse_options.auto_sort` is set.
+    fn check_order(&mut self) {
+        if !self.is_sorted() {
+            self.warnings
+                .push("Subtitles are not in chronological order".to_string());
+            if self.parse_options.auto_sort {
+                self.sort();
+            }
+        }
+    }
+
+    /// Parses a single block of lines into a subtitle.
+    /// Malformed blocks are handled according to `parse_options.mode`.
+    fn parse_block(&mut self, block: &[String], block_number: usize) -> Result<(), SRTError> {
+        let lines: Vec<&str> = block.iter().map(String::as_str).collect();
+        let result = Subtitle::parse_with(&lines, &self.parse_options);
+        self.push_parsed(result, &format!("block {}", block_number))
+    }
+
+    /// Adds a parsed subtitle unless it is invalid. Malformed subtitles are handled according
+    /// to `parse_options.mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The parsed subtitle, or the reason it is malformed.
+    /// * `location` - Where the subtitle was read, e.g. `"block 3"`, used in messages.
+    fn push_parsed(
+        &mut self,
+        result: Result<Subtitle, String>,
+        location: &str,
+    ) -> Result<(), SRTError> {
+        let subtitle = match (result, self.parse_options.mode) {
+            (Ok(subtitle), _) => subtitle,
+            (Err(e), ParseMode::Strict) => {
+                return Err(SRTError::SubtitleParseError(format!("{}: {}", location, e)));
+            }
+            (Err(e), ParseMode::Lenient) => {
+                let warning = format!("{}: {}", location, e);
+                tracing::warn!("Skipping malformed subtitle, {}", warning);
+                self.warnings.push(warning);
+                self.dropped += 1;
+                return Ok(());
             }
+        };
+
+        if subtitle.text.is_empty() || !subtitle.is_valid_with(&self.parse_options.validation) {
+            tracing::debug!("Dropping invalid subtitle in {}", location);
+            self.dropped += 1;
+            return Ok(());
         }
 
+        self.subtitles.push(subtitle);
         Ok(())
     }
 
-    /// Writes the subtitles to the SRT file.
+    /// Writes the subtitles to the SRT file. With the `gzip` feature, files ending in `.gz`
+    /// are compressed.
     ///
     /// # Arguments
     ///
@@ -66,33 +487,3259 @@ impl SRT {
     ///
     /// # Returns
     ///
-    /// * `Result<(), String>` - Returns `Ok(())` if successful, or an error message if it fails.
-    pub fn write_file(&self, file_path: &str) -> Result<(), String> {
+    /// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+    pub fn write_file(&self, file_path: &str) -> Result<usize, String> {
         let file = File::create(file_path).map_err(|e| e.to_string())?;
-        let mut writer = BufWriter::new(file);
+
+        #[cfg(feature = "gzip")]
+        if is_gzip_path(file_path) {
+            let mut encoder =
+                flate2::write::GzEncoder::new(BufWriter::new(file), flate2::Compression::default());
+            let count = self.write_to(&mut encoder)?;
+            encoder.finish().map_err(|e| e.to_string())?;
+            return Ok(count);
+        }
+
+        self.write_to(BufWriter::new(file))
+    }
+
+    /// Writes the subtitles to the given writer, following `write_options`.
+    ///
+    /// With the default write options, the output is canonical: reading it back and writing it
+    /// again yields the same bytes. For that reason, blank lines in the text are left out
+    /// unless `write_options.keep_blank_lines` is set, since they would end the subtitle when
+    /// read back.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the subtitles to.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<usize, String> {
+        self.write_blocks(writer, self.write_options.index_start, false)
+    }
+
+    /// Appends the subtitles to an SRT file, numbering them from the given index instead of
+    /// `write_options.index_start`, e.g. to concatenate the output of several runs. The file
+    /// is created if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - A string representing the path to the SRT file.
+    /// * `start_index` - The index of the first appended subtitle.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The index following the last appended subtitle, or an error message if it fails.
+    pub fn append_to_file(&self, file_path: &str, start_index: usize) -> Result<usize, String> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file_path)
+            .map_err(|e| e.to_string())?;
+        let is_empty = file.metadata().map_err(|e| e.to_string())?.len() == 0;
+        let count = self.write_blocks(BufWriter::new(file), start_index, !is_empty)?;
+        Ok(start_index + count * self.write_options.index_step)
+    }
+
+    /// Writes the subtitles as SRT blocks, see [`SRT::write_to`].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the subtitles to.
+    /// * `start_index` - The index of the first subtitle.
+    /// * `after_block` - Whether the writer already holds a block, which the first subtitle
+    ///   must then be separated from.
+    fn write_blocks<W: Write>(
+        &self,
+        mut writer: W,
+        start_index: usize,
+        after_block: bool,
+    ) -> Result<usize, String> {
+        if self.write_options.strict {
+            self.validate().map_err(|errors| errors.join("; "))?;
+        }
+
+        let eol = self.write_options.line_ending.as_str();
         for (i, subtitle) in self.subtitles.iter().enumerate() {
-            writeln!(writer, "{}", i + 1).map_err(|e| e.to_string())?;
-            writeln!(writer, "{}", subtitle.to_string()).map_err(|e| e.to_string())?;
+            // Blocks are separated by exactly one blank line
+            if (i > 0 || after_block) && self.write_options.blank_separator {
+                write!(writer, "{}", eol).map_err(|e| e.to_string())?;
+            }
+            if self.write_options.indices {
+                let index = start_index + i * self.write_options.index_step;
+                write!(writer, "{}{}", index, eol).map_err(|e| e.to_string())?;
+            }
+            write!(writer, "{} --> {}", subtitle.start_time, subtitle.end_time)
+                .map_err(|e| e.to_string())?;
+            if let Some(settings) = &subtitle.settings {
+                write!(writer, " {}", settings).map_err(|e| e.to_string())?;
+            }
+            write!(writer, "{}", eol).map_err(|e| e.to_string())?;
+            let lines = subtitle.text.lines();
+            for line in
+                lines.filter(|line| self.write_options.keep_blank_lines || !line.trim().is_empty())
+            {
+                write!(writer, "{}{}", line, eol).map_err(|e| e.to_string())?;
+            }
         }
-        Ok(())
+        writer.flush().map_err(|e| e.to_string())?;
+        Ok(self.subtitles.len())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Writes the subtitles to an ASS (SubStation Alpha) file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - A string representing the path to the ASS file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+    pub fn write_ass_file(&self, file_path: &str) -> Result<usize, String> {
+        let file = File::create(file_path).map_err(|e| e.to_string())?;
+        self.write_ass_to(BufWriter::new(file))
+    }
 
-    #[test]
-    fn test_srt_read_file() {
-        let test_file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/test_1/input.srt");
-        let mut srt = SRT::new(test_file_path);
-        assert!(srt.read_file().is_ok());
-        assert!(!srt.subtitles.is_empty());
+    /// Writes the subtitles to an ASS (SubStation Alpha) file, see [`SRT::write_ass_file`].
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - A string representing the path to the ASS file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+    pub fn write_ass(&self, file_path: &str) -> Result<usize, String> {
+        self.write_ass_file(file_path)
+    }
+
+    /// Writes the subtitles to the given writer as a minimal ASS file, with a `[Script Info]`
+    /// section and an `[Events]` section holding one `Dialogue:` line per subtitle in the
+    /// `Default` style. Timestamps are rounded to centiseconds, see [`Timestamp::to_ass`], and
+    /// line breaks are written as `\N`. Lines end as set in `write_options`.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the subtitles to.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+    pub fn write_ass_to<W: Write>(&self, mut writer: W) -> Result<usize, String> {
+        let eol = self.write_options.line_ending.as_str();
+        write!(
+            writer,
+            "[Script Info]{eol}ScriptType: v4.00+{eol}{eol}[Events]{eol}\
+             Format: {}{eol}",
+            ASS_EVENT_FORMAT.join(", ")
+        )
+        .map_err(|e| e.to_string())?;
+        for subtitle in &self.subtitles {
+            write!(
+                writer,
+                "Dialogue: 0,{},{},Default,,0,0,0,,{}{}",
+                subtitle.start_time.to_ass(),
+                subtitle.end_time.to_ass(),
+                subtitle.text.lines().collect::<Vec<_>>().join("\\N"),
+                eol
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())?;
+        Ok(self.subtitles.len())
+    }
+
+    /// Writes the subtitles to a WebVTT file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - A string representing the path to the VTT file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+    pub fn write_vtt_file(&self, file_path: &str) -> Result<usize, String> {
+        let file = File::create(file_path).map_err(|e| e.to_string())?;
+        self.write_vtt_to(BufWriter::new(file))
+    }
+
+    /// Writes the subtitles to the given writer in the WebVTT format, following `write_options`.
+    /// Cues are written without identifiers.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the subtitles to.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, String>` - The number of subtitles written, or an error message if it fails.
+    pub fn write_vtt_to<W: Write>(&self, mut writer: W) -> Result<usize, String> {
+        let eol = self.write_options.line_ending.as_str();
+        write!(writer, "WEBVTT{}", eol).map_err(|e| e.to_string())?;
+        for subtitle in &self.subtitles {
+            write!(writer, "{}", eol).map_err(|e| e.to_string())?;
+            write!(
+                writer,
+                "{} --> {}{}",
+                subtitle.start_time.to_vtt(),
+                subtitle.end_time.to_vtt(),
+                eol
+            )
+            .map_err(|e| e.to_string())?;
+            for line in subtitle.text.lines() {
+                write!(writer, "{}{}", line, eol).map_err(|e| e.to_string())?;
+            }
+        }
+        writer.flush().map_err(|e| e.to_string())?;
+        Ok(self.subtitles.len())
+    }
+
+    /// Sorts the subtitles chronologically by start time, then by end time.
+    /// The sort is stable, so subtitles with the same timing keep their relative order.
+    pub fn sort(&mut self) {
+        self.subtitles.sort_by(|a, b| {
+            a.start_time
+                .cmp(&b.start_time)
+                .then_with(|| a.end_time.cmp(&b.end_time))
+        });
+    }
+
+    /// Checks whether the subtitles are in chronological order of start time.
+    pub fn is_sorted(&self) -> bool {
+        self.subtitles
+            .is_sorted_by(|a, b| a.start_time <= b.start_time)
+    }
+
+    /// Finds the first subtitle out of chronological order, see [`SRT::is_sorted`].
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The position (in `subtitles`) of the first subtitle starting before
+    ///   the previous one, or `None` if the subtitles are sorted.
+    pub fn first_order_violation(&self) -> Option<usize> {
+        self.subtitles
+            .windows(2)
+            .position(|pair| pair[1].start_time < pair[0].start_time)
+            .map(|i| i + 1)
+    }
+
+    /// Assigns sequential indices, starting at 1, to the subtitles in their current order.
+    pub fn renumber(&mut self) {
+        for (i, subtitle) in self.subtitles.iter_mut().enumerate() {
+            subtitle.index = i + 1;
+        }
+    }
+
+    /// Finds the subtitles that overlap the subtitle that follows them.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The positions of the subtitles ending after the next subtitle starts.
+    pub fn find_overlaps(&self) -> Vec<usize> {
+        self.subtitles
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0].end_time > pair[1].start_time)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Checks that the subtitles are internally consistent: every subtitle ends at or after its
+    /// start, its timestamps fit the SRT format (see [`Timestamp::is_srt_representable`]), and,
+    /// if `write_options.reject_overlaps` is set, none overlaps the next one. Timestamps are
+    /// unsigned, so they can't be negative.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Vec<String>>` - Returns `Ok(())` if the subtitles are consistent, or every
+    ///   problem found, referring to subtitles by their 1-based position.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = self
+            .subtitles
+            .iter()
+            .enumerate()
+            .filter(|(_, subtitle)| subtitle.end_time < subtitle.start_time)
+            .map(|(i, _)| format!("Subtitle {}: End time before start time", i + 1))
+            .collect();
+
+        errors.extend(
+            self.subtitles
+                .iter()
+                .enumerate()
+                .filter(|(_, subtitle)| {
+                    !subtitle.start_time.is_srt_representable()
+                        || !subtitle.end_time.is_srt_representable()
+                })
+                .map(|(i, _)| format!("Subtitle {}: Timestamp exceeds 99 hours", i + 1)),
+        );
+
+        if self.write_options.reject_overlaps {
+            errors.extend(
+                self.find_overlaps()
+                    .into_iter()
+                    .map(|i| format!("Subtitle {}: Overlaps the next subtitle", i + 1)),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Appends the subtitles of another SRT, shifted forward by `offset`.
+    ///
+    /// The appended subtitles that are invalid according to `parse_options.validation` are
+    /// dropped and counted in [`SRT::dropped`], while the existing subtitles are kept as is.
+    /// The merged subtitles are then sorted and renumbered, and any resulting overlaps are
+    /// logged as warnings.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The SRT to append.
+    /// * `offset` - The offset to apply to the appended subtitles.
+    pub fn append(&mut self, other: &SRT, offset: Duration) {
+        for subtitle in &other.subtitles {
+            if subtitle.text.is_empty() || !subtitle.is_valid_with(&self.parse_options.validation) {
+                tracing::debug!(
+                    "Dropping invalid subtitle {} of {}",
+                    subtitle.index,
+                    other.file_path
+                );
+                self.dropped += 1;
+                continue;
+            }
+            self.subtitles.push(Subtitle {
+                start_time: subtitle.start_time.clone() + offset,
+                end_time: subtitle.end_time.clone() + offset,
+                ..subtitle.clone()
+            });
+        }
+
+        self.sort();
+        self.renumber();
+        for i in self.find_overlaps() {
+            tracing::warn!(
+                "Subtitle {} overlaps the next subtitle after appending {}",
+                i + 1,
+                other.file_path
+            );
+        }
+    }
+
+    /// Splits the subtitles into two SRTs at the given timestamp.
+    ///
+    /// The first SRT keeps the subtitles starting before `at`, with the end of subtitles
+    /// straddling `at` clamped to it; the rest of a straddling subtitle is dropped. The second
+    /// SRT gets the subtitles starting at or after `at`, shifted back by `at`. Every subtitle
+    /// ends up in exactly one of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - The timestamp to split at.
+    ///
+    /// # Returns
+    ///
+    /// * `(SRT, SRT)` - The subtitles before and after `at`.
+    pub fn split_at(&self, at: Timestamp) -> (SRT, SRT) {
+        let offset = Duration::from_millis(at.to_millis());
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        for subtitle in &self.subtitles {
+            if subtitle.start_time < at {
+                before.push(Subtitle {
+                    end_time: subtitle.end_time.clone().min(at.clone()),
+                    ..subtitle.clone()
+                });
+            } else {
+                after.push(Subtitle {
+                    start_time: subtitle.start_time.clone() - offset,
+                    end_time: subtitle.end_time.clone() - offset,
+                    ..subtitle.clone()
+                });
+            }
+        }
+
+        (self.with_subtitles(before), self.with_subtitles(after))
     }
 
-    // #[test]
-    // fn test_srt_write_file() {
-    //     let srt = SRT::new("test.srt");
-    //     assert!(srt.write_file("output.srt").is_ok());
-    // }
+    /// Extracts the subtitles within a time window.
+    ///
+    /// Subtitles overlapping the window are clamped to its edges, and every timestamp is
+    /// rebased so that `from` becomes `00:00:00,000`.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The start of the window.
+    /// * `to` - The end of the window.
+    ///
+    /// # Returns
+    ///
+    /// * `SRT` - The subtitles within the window.
+    pub fn slice(&self, from: Timestamp, to: Timestamp) -> SRT {
+        let offset = Duration::from_millis(from.to_millis());
+        let subtitles = self
+            .subtitles
+            .iter()
+            .filter(|subtitle| subtitle.start_time < to && subtitle.end_time > from)
+            .map(|subtitle| Subtitle {
+                start_time: subtitle.start_time.clone().max(from.clone()) - offset,
+                end_time: subtitle.end_time.clone().min(to.clone()) - offset,
+                ..subtitle.clone()
+            })
+            .collect();
+
+        self.with_subtitles(subtitles)
+    }
+
+    /// Creates a new SRT with the same file path and options, but different subtitles.
+    fn with_subtitles(&self, subtitles: Vec<Subtitle>) -> SRT {
+        SRT {
+            file_path: self.file_path.clone(),
+            subtitles,
+            parse_options: self.parse_options.clone(),
+            write_options: self.write_options.clone(),
+            warnings: Vec::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Computes summary statistics of the subtitles.
+    ///
+    /// Gaps are measured between consecutive subtitles in their current order; overlapping
+    /// subtitles do not contribute to the total gap.
+    ///
+    /// # Returns
+    ///
+    /// * `SrtStats` - The statistics of the file. Durations are zero if there are no subtitles.
+    pub fn stats(&self) -> SrtStats {
+        let durations: Vec<Duration> = self.subtitles.iter().map(|s| s.duration()).collect();
+        let total_on_screen: Duration = durations.iter().sum();
+
+        let total_gap = self.gaps().iter().map(|(_, gap)| *gap).sum();
+
+        let total_chars: usize = self
+            .subtitles
+            .iter()
+            .map(|s| s.text.chars().filter(|c| !c.is_whitespace()).count())
+            .sum();
+        let average_cps = if total_on_screen.is_zero() {
+            0.0
+        } else {
+            total_chars as f64 / total_on_screen.as_secs_f64()
+        };
+
+        SrtStats {
+            cue_count: self.subtitles.len(),
+            total_on_screen,
+            total_gap,
+            longest: durations.iter().max().copied().unwrap_or_default(),
+            shortest: durations.iter().min().copied().unwrap_or_default(),
+            average_cps,
+        }
+    }
+
+    /// Finds the subtitles whose reading speed exceeds the given characters per second.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_cps` - The maximum allowed characters per second.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The positions (in `subtitles`) of the subtitles that are too fast.
+    pub fn find_fast(&self, max_cps: f64) -> Vec<usize> {
+        self.subtitles
+            .iter()
+            .enumerate()
+            .filter(|(_, subtitle)| subtitle.cps() > max_cps)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Extends subtitles shorter than `min` so they stay on screen for at least `min`.
+    ///
+    /// A subtitle is never extended past the start of the following subtitle; its end is
+    /// clamped to one millisecond before the next start instead. The last subtitle is
+    /// extended freely.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum display duration.
+    pub fn enforce_min_duration(&mut self, min: Duration) {
+        let min = min.as_millis() as u64;
+        for i in 0..self.subtitles.len() {
+            let start = self.subtitles[i].start_time.to_millis();
+            let end = self.subtitles[i].end_time.to_millis();
+            if end.saturating_sub(start) >= min {
+                continue;
+            }
+
+            let mut new_end = start + min;
+            if let Some(next) = self.subtitles.get(i + 1) {
+                let next_start = next.start_time.to_millis();
+                new_end = new_end.min(next_start.saturating_sub(1));
+            }
+
+            if new_end > end {
+                self.subtitles[i].end_time = Timestamp::from_millis(new_end);
+            }
+        }
+    }
+
+    /// Finds the gaps between consecutive subtitles.
+    ///
+    /// Abutting and overlapping subtitles are not reported.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(usize, Duration)>` - The position of the earlier subtitle of each pair, and the
+    ///   duration of the gap that follows it.
+    pub fn gaps(&self) -> Vec<(usize, Duration)> {
+        self.subtitles
+            .windows(2)
+            .enumerate()
+            .filter_map(|(i, pair)| {
+                let end = pair[0].end_time.to_millis();
+                let next_start = pair[1].start_time.to_millis();
+                (next_start > end).then(|| (i, Duration::from_millis(next_start - end)))
+            })
+            .collect()
+    }
+
+    /// Finds the subtitles followed by a gap that is positive but shorter than `min`.
+    /// Such subtitles should rather abut or be merged with the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum comfortable gap between consecutive subtitles.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The positions (in `subtitles`) of the subtitles followed by a tight gap.
+    pub fn find_tight_gaps(&self, min: Duration) -> Vec<usize> {
+        self.gaps()
+            .into_iter()
+            .filter(|(_, gap)| *gap < min)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Finds the subtitles with a blank line amid their text.
+    ///
+    /// See [`Subtitle::has_internal_blank_lines`] and [`Subtitle::compact_lines`].
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The positions (in `subtitles`) of the subtitles with internal blank
+    ///   lines.
+    pub fn find_internal_blanks(&self) -> Vec<usize> {
+        self.subtitles
+            .iter()
+            .enumerate()
+            .filter(|(_, subtitle)| subtitle.has_internal_blank_lines())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Removes formatting tags from the text of every subtitle.
+    ///
+    /// See [`Subtitle::strip_tags`].
+    pub fn strip_tags(&mut self) {
+        for subtitle in &mut self.subtitles {
+            subtitle.strip_tags();
+        }
+    }
+
+    /// Removes the subtitles whose text is empty or whitespace-only from the front and back
+    /// of the list. This is meant to run after text cleaning, such as [`SRT::strip_tags`].
+    ///
+    /// # Arguments
+    ///
+    /// * `everywhere` - Whether to also remove empty subtitles from the middle of the list.
+    pub fn trim_empty(&mut self, everywhere: bool) {
+        let is_empty = |subtitle: &Subtitle| subtitle.text.trim().is_empty();
+        if everywhere {
+            self.subtitles.retain(|subtitle| !is_empty(subtitle));
+            return;
+        }
+
+        let start = self
+            .subtitles
+            .iter()
+            .position(|subtitle| !is_empty(subtitle))
+            .unwrap_or(self.subtitles.len());
+        let end = self
+            .subtitles
+            .iter()
+            .rposition(|subtitle| !is_empty(subtitle))
+            .map_or(start, |i| i + 1);
+        self.subtitles.truncate(end);
+        self.subtitles.drain(..start);
+    }
+
+    /// Removes sound-effect markers from every subtitle, then drops the subtitles left
+    /// without any text.
+    ///
+    /// See [`Subtitle::remove_sound_cues`].
+    pub fn remove_sound_cues(&mut self) {
+        for subtitle in &mut self.subtitles {
+            subtitle.remove_sound_cues();
+        }
+        self.trim_empty(true);
+    }
+
+    /// Linearly retimes every subtitle using two sync points.
+    ///
+    /// The mapping `new = to1 + (old - from1) * scale` is computed so that `from1` maps to
+    /// `to1` and `from2` maps to `to2`. Results are rounded to the nearest millisecond and
+    /// clamped at zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `from1` - The current time of the first sync point.
+    /// * `to1` - The correct time of the first sync point.
+    /// * `from2` - The current time of the second sync point.
+    /// * `to2` - The correct time of the second sync point.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error if both sync points share the same current time.
+    pub fn retime(
+        &mut self,
+        from1: Timestamp,
+        to1: Timestamp,
+        from2: Timestamp,
+        to2: Timestamp,
+    ) -> Result<(), SRTError> {
+        if from1 == from2 {
+            return Err(SRTError::InvalidInput(
+                "Sync points must have different times".to_string(),
+            ));
+        }
+
+        let from1 = from1.to_millis() as f64;
+        let to1 = to1.to_millis() as f64;
+        let scale = (to2.to_millis() as f64 - to1) / (from2.to_millis() as f64 - from1);
+        let map = |timestamp: &Timestamp| {
+            let millis = to1 + (timestamp.to_millis() as f64 - from1) * scale;
+            Timestamp::from_millis(millis.round().max(0.0) as u64)
+        };
+
+        for subtitle in &mut self.subtitles {
+            subtitle.start_time = map(&subtitle.start_time);
+            subtitle.end_time = map(&subtitle.end_time);
+        }
+
+        Ok(())
+    }
+
+    /// Converts the subtitle timings from one frame rate to another, e.g. to undo a PAL
+    /// speedup (`25` → `23.976`).
+    ///
+    /// Every timestamp is multiplied by `from_fps / to_fps`, rounded to the nearest
+    /// millisecond.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_fps` - The frame rate the subtitles are currently timed for.
+    /// * `to_fps` - The frame rate to convert the subtitles to.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error if either frame rate is not positive.
+    pub fn convert_framerate(&mut self, from_fps: f64, to_fps: f64) -> Result<(), SRTError> {
+        if !(from_fps > 0.0 && to_fps > 0.0) {
+            return Err(SRTError::InvalidInput(format!(
+                "Frame rates must be positive, got {} and {}",
+                from_fps, to_fps
+            )));
+        }
+
+        self.scale(from_fps / to_fps)
+    }
+
+    /// Scales every timestamp by a speed factor, e.g. `1.05` to slow down or `0.95` to speed
+    /// up. Results are rounded to the nearest millisecond.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The factor to multiply every timestamp by.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error if the factor is not positive.
+    pub fn scale(&mut self, factor: f64) -> Result<(), SRTError> {
+        if !(factor > 0.0 && factor.is_finite()) {
+            return Err(SRTError::InvalidInput(format!(
+                "Scale factor must be positive, got {}",
+                factor
+            )));
+        }
+
+        let multiply = |timestamp: &Timestamp| {
+            let millis = (timestamp.to_millis() as f64 * factor).round();
+            Timestamp::from_millis(millis as u64)
+        };
+
+        for subtitle in &mut self.subtitles {
+            subtitle.start_time = multiply(&subtitle.start_time);
+            subtitle.end_time = multiply(&subtitle.end_time);
+        }
+
+        Ok(())
+    }
+
+    /// Snaps every start and end time to the nearest frame boundary at the given frame rate.
+    /// Halfway cases are rounded away from zero, see [`Timestamp::round_to_frame`].
+    ///
+    /// # Arguments
+    ///
+    /// * `fps` - The frame rate, must be positive.
+    pub fn snap_to_frames(&mut self, fps: f64) {
+        for subtitle in &mut self.subtitles {
+            subtitle.start_time.round_to_frame(fps);
+            subtitle.end_time.round_to_frame(fps);
+        }
+    }
+
+    /// Moves the subtitles starting close to a scene cut so that they start on it, keeping
+    /// their duration. Each subtitle snaps to the closest cut within the tolerance, the others
+    /// are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `cuts` - The timestamps of the scene cuts, in any order.
+    /// * `tolerance` - The maximum distance between the start of a subtitle and a cut for the
+    ///   subtitle to snap to it.
+    pub fn snap_starts_to(&mut self, cuts: &[Timestamp], tolerance: Duration) {
+        for subtitle in &mut self.subtitles {
+            let closest = cuts
+                .iter()
+                .min_by_key(|cut| subtitle.start_time.delta_to(cut).0)
+                .filter(|cut| subtitle.start_time.delta_to(cut).0 <= tolerance);
+            if let Some(cut) = closest {
+                let duration = subtitle.duration();
+                subtitle.start_time = cut.clone();
+                subtitle.end_time = cut.saturating_add(duration);
+            }
+        }
+    }
+
+    /// Re-wraps the text of every subtitle so that no line exceeds `max_chars` characters.
+    ///
+    /// See [`Subtitle::wrap`].
+    pub fn wrap_all(&mut self, max_chars: usize) {
+        for subtitle in &mut self.subtitles {
+            subtitle.wrap(max_chars);
+        }
+    }
+
+    /// Joins the lines of every subtitle into a single line.
+    ///
+    /// See [`Subtitle::unwrap`].
+    pub fn unwrap_all(&mut self) {
+        for subtitle in &mut self.subtitles {
+            subtitle.unwrap();
+        }
+    }
+
+    /// Converts the text of every subtitle to uppercase.
+    ///
+    /// See [`Subtitle::to_uppercase`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_uppercase(&mut self) {
+        for subtitle in &mut self.subtitles {
+            subtitle.to_uppercase();
+        }
+    }
+
+    /// Converts the text of every subtitle to lowercase.
+    ///
+    /// See [`Subtitle::to_lowercase`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_lowercase(&mut self) {
+        for subtitle in &mut self.subtitles {
+            subtitle.to_lowercase();
+        }
+    }
+
+    /// Shortens every subtitle longer than `max` so its duration equals `max`.
+    /// Start times and other subtitles are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum display duration.
+    pub fn clamp_duration(&mut self, max: Duration) {
+        for subtitle in &mut self.subtitles {
+            if subtitle.duration() > max {
+                subtitle.end_time = subtitle.start_time.clone() + max;
+            }
+        }
+    }
+
+    /// Computes the span of the subtitles, from the earliest start to the latest end.
+    /// The subtitles don't need to be sorted.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Duration>` - The span of the subtitles, or `None` if there are no subtitles.
+    pub fn total_span(&self) -> Option<Duration> {
+        let start = self.subtitles.iter().map(|s| &s.start_time).min()?;
+        let end = self.subtitles.iter().map(|s| &s.end_time).max()?;
+        Some(end - start)
+    }
+
+    /// Merges adjacent subtitles separated by a small gap into a single subtitle spanning
+    /// both, joining their text.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The maximum gap between two subtitles for them to be merged, and the
+    ///   separator joining their text. Without a `max_gap`, only subtitles that touch or
+    ///   overlap are merged.
+    pub fn merge_close(&mut self, options: &MergeOptions) {
+        let max_gap = options.max_gap.unwrap_or(Duration::ZERO);
+        let mut merged: Vec<Subtitle> = Vec::with_capacity(self.subtitles.len());
+        for subtitle in self.subtitles.drain(..) {
+            if let Some(previous) = merged.last_mut() {
+                let gap = &subtitle.start_time - &previous.end_time;
+                if gap <= max_gap {
+                    previous.end_time = previous.end_time.clone().max(subtitle.end_time);
+                    previous.text = format!(
+                        "{}{}{}",
+                        previous.text, options.join_separator, subtitle.text
+                    );
+                    continue;
+                }
+            }
+            merged.push(subtitle);
+        }
+        self.subtitles = merged;
+    }
+
+    /// Merges adjacent subtitles with identical text into a single subtitle spanning both.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The maximum gap between two subtitles for them to be merged. The join
+    ///   separator is unused since the text is kept as is.
+    pub fn merge_identical_adjacent(&mut self, options: &MergeOptions) {
+        let mut merged: Vec<Subtitle> = Vec::with_capacity(self.subtitles.len());
+        for subtitle in self.subtitles.drain(..) {
+            if let Some(previous) = merged.last_mut() {
+                let gap = &subtitle.start_time - &previous.end_time;
+                if previous.text == subtitle.text && options.max_gap.is_none_or(|max| gap <= max) {
+                    previous.end_time = previous.end_time.clone().max(subtitle.end_time);
+                    continue;
+                }
+            }
+            merged.push(subtitle);
+        }
+        self.subtitles = merged;
+    }
+
+    /// Removes the invalid subtitles according to `parse_options.validation`.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(Subtitle, String)>` - The removed subtitles, with their original indices, paired
+    ///   with the reason they were removed.
+    pub fn clean(&mut self) -> Vec<(Subtitle, String)> {
+        let mut removed = Vec::new();
+        let mut kept = Vec::with_capacity(self.subtitles.len());
+        for subtitle in self.subtitles.drain(..) {
+            match subtitle.invalid_reason_with(&self.parse_options.validation) {
+                Some(reason) => removed.push((subtitle, reason)),
+                None => kept.push(subtitle),
+            }
+        }
+        self.subtitles = kept;
+        removed
+    }
+
+    /// Swaps the start and end time of the subtitles ending before they start, as written by
+    /// some buggy tools. Unlike clamping, this assumes both values are right but transposed.
+    ///
+    /// Reading a file rejects such subtitles as malformed, so this applies to subtitles built
+    /// or edited in memory.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of subtitles fixed.
+    pub fn fix_reversed(&mut self) -> usize {
+        let mut fixed = 0;
+        for subtitle in &mut self.subtitles {
+            if subtitle.start_time > subtitle.end_time {
+                std::mem::swap(&mut subtitle.start_time, &mut subtitle.end_time);
+                fixed += 1;
+            }
+        }
+        fixed
+    }
+
+    /// Sets the end time of the last subtitle, e.g. to keep an outro caption on screen until
+    /// the end of the video. The end time may also be earlier than the current one.
+    ///
+    /// # Arguments
+    ///
+    /// * `end` - The new end time of the last subtitle.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Returns `Ok(())` if successful, or an error message if there are no subtitles or `end` is before the start of the last one.
+    pub fn extend_last_to(&mut self, end: Timestamp) -> Result<(), String> {
+        self.subtitles
+            .last_mut()
+            .ok_or("No subtitles")?
+            .set_end(end)
+    }
+
+    /// Trims the subtitles to the length of the video. Subtitles starting at or after the end
+    /// of the video are removed, and the end of the subtitles extending past it is clamped.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The length of the video.
+    pub fn clamp_to_length(&mut self, length: Duration) {
+        let end = Timestamp::from_millis(length.as_millis() as u64);
+        self.subtitles.retain(|subtitle| subtitle.start_time < end);
+        for subtitle in &mut self.subtitles {
+            if subtitle.end_time > end {
+                subtitle.end_time = end.clone();
+            }
+        }
+    }
+
+    /// Sets every gap between consecutive subtitles to the given duration.
+    ///
+    /// Each subtitle after the first is moved so that it starts `gap` after the end of the
+    /// previous one, keeping its duration. Subtitles are processed in order, so every move
+    /// carries over to the following subtitles.
+    ///
+    /// # Arguments
+    ///
+    /// * `gap` - The gap to leave between consecutive subtitles.
+    pub fn set_gap(&mut self, gap: Duration) {
+        for i in 1..self.subtitles.len() {
+            let start_time = self.subtitles[i - 1].end_time.clone() + gap;
+            let subtitle = &mut self.subtitles[i];
+            let duration = &subtitle.end_time - &subtitle.start_time;
+            subtitle.end_time = start_time.clone() + duration;
+            subtitle.start_time = start_time;
+        }
+    }
+
+    /// Moves every subtitle by the given duration in the specified direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The delta to move the subtitles by.
+    /// * `direction` - The direction to move the subtitles in (forward or backward).
+    /// * `clamp` - How subtitles moved before `00:00:00,000` are handled. In
+    ///   [`ClampMode::Error`] mode, no subtitle is moved if any of them would be clamped.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error naming the first subtitle that would be clamped.
+    pub fn shift_all(
+        &mut self,
+        delta: Duration,
+        direction: Direction,
+        clamp: ClampMode,
+    ) -> Result<(), SRTError> {
+        if clamp == ClampMode::Error && matches!(direction, Direction::Backward) {
+            if let Some(i) = self
+                .subtitles
+                .iter()
+                .position(|subtitle| subtitle.start_time.checked_sub(delta).is_none())
+            {
+                return Err(SRTError::TimeError(format!(
+                    "Subtitle {} would be moved before 00:00:00,000",
+                    i + 1
+                )));
+            }
+        }
+
+        for subtitle in &mut self.subtitles {
+            subtitle.offset(&delta, &direction)?;
+        }
+        Ok(())
+    }
+
+    /// Moves a range of subtitles by the given duration in the specified direction.
+    /// Both the start and end time of each subtitle in the range are moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_index` - The position (in `subtitles`) of the first subtitle to move.
+    /// * `to_index` - The position of the last subtitle to move, inclusive.
+    /// * `delta` - The delta to move the subtitles by.
+    /// * `direction` - The direction to move the subtitles in (forward or backward).
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error if the range is out of bounds.
+    pub fn shift_range(
+        &mut self,
+        from_index: usize,
+        to_index: usize,
+        delta: Duration,
+        direction: Direction,
+    ) -> Result<(), SRTError> {
+        if from_index > to_index || to_index >= self.subtitles.len() {
+            return Err(SRTError::InvalidInput(format!(
+                "Invalid range {}..={} for {} subtitles",
+                from_index,
+                to_index,
+                self.subtitles.len()
+            )));
+        }
+
+        for subtitle in &mut self.subtitles[from_index..=to_index] {
+            subtitle.offset(&delta, &direction)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces every occurrence of `find` with `replace` in the text of the subtitles.
+    /// Timestamps are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `find` - The text to search for, must not be empty.
+    /// * `replace` - The text to replace it with.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of subtitles whose text changed.
+    pub fn replace_text(&mut self, find: &str, replace: &str) -> usize {
+        if find.is_empty() {
+            return 0;
+        }
+        let mut changed = 0;
+        for subtitle in &mut self.subtitles {
+            if subtitle.text.contains(find) {
+                let text = subtitle.text.replace(find, replace);
+                if text != subtitle.text {
+                    subtitle.text = text;
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Replaces every match of `pattern` with `replace` in the text of the subtitles.
+    /// Timestamps are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regular expression to search for.
+    /// * `replace` - The replacement, which may refer to capture groups (e.g. `$1`).
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of subtitles whose text changed.
+    #[cfg(feature = "regex")]
+    pub fn replace_regex(&mut self, pattern: &regex::Regex, replace: &str) -> usize {
+        let mut changed = 0;
+        for subtitle in &mut self.subtitles {
+            let text = pattern.replace_all(&subtitle.text, replace);
+            if text != subtitle.text {
+                subtitle.text = text.into_owned();
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Counts the subtitles matching a predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `pred` - The predicate to test each subtitle with.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of subtitles for which `pred` returns `true`.
+    pub fn count_matching<F: Fn(&Subtitle) -> bool>(&self, pred: F) -> usize {
+        self.subtitles
+            .iter()
+            .filter(|subtitle| pred(subtitle))
+            .count()
+    }
+
+    /// Counts the subtitles whose text contains a phrase. The search is case-sensitive.
+    ///
+    /// # Arguments
+    ///
+    /// * `phrase` - The phrase to search for.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of subtitles containing `phrase`.
+    pub fn count_containing(&self, phrase: &str) -> usize {
+        self.count_matching(|subtitle| subtitle.text.contains(phrase))
+    }
+
+    /// Brings the subtitles into a canonical form without dropping any of them: the text is
+    /// normalized (see [`Subtitle::normalize_text`]), and the subtitles are sorted and
+    /// renumbered. Unlike reading, no validity filter is applied.
+    pub fn normalize(&mut self) {
+        for subtitle in &mut self.subtitles {
+            subtitle.normalize_text();
+        }
+        self.sort();
+        self.renumber();
+    }
+
+    /// Merges adjacent subtitles sharing exactly the same start and end time into a single
+    /// subtitle, joining their text.
+    ///
+    /// Unlike [`SRT::merge_identical_adjacent`], the text of the merged subtitles may differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The separator joining the text of the merged subtitles. The maximum gap
+    ///   is unused since only subtitles with the same timing are merged.
+    pub fn merge_same_time(&mut self, options: &MergeOptions) {
+        let mut merged: Vec<Subtitle> = Vec::with_capacity(self.subtitles.len());
+        for subtitle in self.subtitles.drain(..) {
+            if let Some(previous) = merged.last_mut() {
+                if previous.start_time == subtitle.start_time
+                    && previous.end_time == subtitle.end_time
+                {
+                    previous.text = format!(
+                        "{}{}{}",
+                        previous.text, options.join_separator, subtitle.text
+                    );
+                    continue;
+                }
+            }
+            merged.push(subtitle);
+        }
+        self.subtitles = merged;
+    }
+
+    /// Builds a plain-text transcript of the subtitles, without indices or timestamps.
+    /// The text of each subtitle forms a paragraph, and paragraphs are separated by a blank line.
+    ///
+    /// # Arguments
+    ///
+    /// * `collapse_lines` - Whether to join the lines of multi-line subtitles with spaces.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The transcript.
+    pub fn to_transcript(&self, collapse_lines: bool) -> String {
+        self.subtitles
+            .iter()
+            .map(|subtitle| {
+                if collapse_lines {
+                    subtitle.text.lines().collect::<Vec<_>>().join(" ")
+                } else {
+                    subtitle.text.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Inserts a subtitle starting at `00:00:00,000` before all the others, e.g. to credit
+    /// the translator. The other subtitles are not moved: if the new subtitle would overlap
+    /// the first one, it is shortened to end where the first one starts. The subtitles are
+    /// renumbered.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text of the new subtitle.
+    /// * `duration` - How long the new subtitle is displayed.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), SRTError>` - Returns `Ok(())` if successful, or an error if the first subtitle starts at `00:00:00,000`, leaving no room.
+    pub fn prepend_cue(&mut self, text: String, duration: Duration) -> Result<(), SRTError> {
+        let mut end_time = Timestamp::from_millis(0) + duration;
+        if let Some(first) = self.subtitles.first() {
+            if first.start_time.to_millis() == 0 {
+                return Err(SRTError::InvalidInput(
+                    "The first subtitle starts at 00:00:00,000".to_string(),
+                ));
+            }
+            end_time = end_time.min(first.start_time.clone());
+        }
+
+        self.subtitles.insert(
+            0,
+            Subtitle {
+                index: 0,
+                start_time: Timestamp::from_millis(0),
+                end_time,
+                text,
+                settings: None,
+            },
+        );
+        self.renumber();
+        Ok(())
+    }
+
+    /// Finds the subtitles whose text is only made up of numbers or timestamps, which usually
+    /// comes from a misparsed file. Unlike [`Subtitle::is_valid`], this is only a heuristic to
+    /// review the subtitles.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The positions (in `subtitles`) of the suspicious subtitles.
+    pub fn find_suspicious(&self) -> Vec<usize> {
+        self.subtitles
+            .iter()
+            .enumerate()
+            .filter(|(_, subtitle)| {
+                let mut lines = subtitle.text.lines().filter(|l| !l.trim().is_empty());
+                lines.clone().next().is_some() && lines.all(is_index_or_timestamp)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Finds the subtitles with more lines of text than the given maximum, which most style
+    /// guides set to two.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_lines` - The maximum number of lines per subtitle.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The positions (in `subtitles`) of the subtitles with too many lines.
+    pub fn find_too_many_lines(&self, max_lines: usize) -> Vec<usize> {
+        self.subtitles
+            .iter()
+            .enumerate()
+            .filter(|(_, subtitle)| subtitle.line_count() > max_lines)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Finds the lines of text longer than the given number of characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of characters per line, see [`Subtitle::line_lengths`].
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(usize, usize)>` - The position (in `subtitles`) of each subtitle with a long
+    ///   line, and the 0-based number of the line within its text.
+    pub fn find_long_lines(&self, max: usize) -> Vec<(usize, usize)> {
+        self.subtitles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, subtitle)| {
+                subtitle
+                    .line_lengths()
+                    .into_iter()
+                    .enumerate()
+                    .filter(move |&(_, length)| length > max)
+                    .map(move |(line, _)| (i, line))
+            })
+            .collect()
+    }
+
+    /// Compares the subtitles with those of another version of the file.
+    ///
+    /// A subtitle is matched with the first unmatched subtitle of `other` whose time range
+    /// overlaps its own. Indices are ignored, so renumbering alone is not reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The new version of the file.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<DiffEntry>` - The removed and modified subtitles in the order of `self`, followed
+    ///   by the added subtitles in the order of `other`.
+    pub fn diff(&self, other: &SRT) -> Vec<DiffEntry> {
+        let overlaps = |a: &Subtitle, b: &Subtitle| {
+            (a.start_time < b.end_time && b.start_time < a.end_time)
+                || (a.start_time == b.start_time && a.end_time == b.end_time)
+        };
+
+        let mut matched = vec![false; other.subtitles.len()];
+        let mut entries = Vec::new();
+        for before in &self.subtitles {
+            let found = (0..other.subtitles.len())
+                .find(|&j| !matched[j] && overlaps(before, &other.subtitles[j]));
+            let Some(j) = found else {
+                entries.push(DiffEntry::Removed(before.clone()));
+                continue;
+            };
+            matched[j] = true;
+
+            let after = &other.subtitles[j];
+            let time_changed =
+                before.start_time != after.start_time || before.end_time != after.end_time;
+            let text_changed = before.text != after.text;
+            if time_changed || text_changed {
+                entries.push(DiffEntry::Modified {
+                    before: before.clone(),
+                    after: after.clone(),
+                    time_changed,
+                    text_changed,
+                });
+            }
+        }
+
+        entries.extend(
+            other
+                .subtitles
+                .iter()
+                .zip(matched)
+                .filter(|(_, matched)| !matched)
+                .map(|(after, _)| DiffEntry::Added(after.clone())),
+        );
+        entries
+    }
+
+    /// Computes a stable fingerprint of the subtitles, e.g. to detect files that were already
+    /// processed.
+    ///
+    /// Only the timestamps and text of the subtitles are hashed, in order. Indices are
+    /// ignored, and so are differences in whitespace within the text. The hash is the same
+    /// across runs and platforms.
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The hash of the subtitles.
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = 0xcbf2_9ce4_8422_2325;
+        for subtitle in &self.subtitles {
+            hash = fnv1a(hash, &subtitle.start_time.to_millis().to_le_bytes());
+            hash = fnv1a(hash, &subtitle.end_time.to_millis().to_le_bytes());
+            for word in subtitle.text.split_whitespace() {
+                hash = fnv1a(hash, word.as_bytes());
+                hash = fnv1a(hash, b" ");
+            }
+            // Separate the text from the next subtitle
+            hash = fnv1a(hash, &[0]);
+        }
+        hash
+    }
+
+    /// Returns an iterator over the subtitles that allows modifying them in place.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Subtitle> {
+        self.subtitles.iter_mut()
+    }
+
+    /// Serializes the SRT into a JSON string.
+    ///
+    /// Timestamps are serialized in their `HH:MM:SS,mmm` form.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, String>` - Returns the JSON string if successful, or an error message if it fails.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+}
+
+/// Iterates over the subtitles mutably, see [`SRT::iter_mut`].
+impl<'a> IntoIterator for &'a mut SRT {
+    type Item = &'a mut Subtitle;
+    type IntoIter = std::slice::IterMut<'a, Subtitle>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::{BlankLineMode, LineEnding};
+
+    fn subtitle(start: &str, end: &str, text: &str) -> Subtitle {
+        Subtitle {
+            start_time: Timestamp::from_string(start).unwrap(),
+            end_time: Timestamp::from_string(end).unwrap(),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_srt_read_file() {
+        let test_file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/test_1/input.srt");
+        let mut srt = SRT::new(test_file_path);
+        assert!(srt.read_file().is_ok());
+        assert!(!srt.subtitles.is_empty());
+    }
+
+    #[test]
+    fn test_srt_read_file_windows_1256() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/encoding/windows_1256.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+
+        assert_eq!(srt.subtitles.len(), 2);
+        assert_eq!(srt.subtitles[0].text, "مرحبا بكم");
+        assert_eq!(srt.subtitles[1].text, "شو قال؟");
+    }
+
+    #[test]
+    fn test_srt_read_file_utf8_bom() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/encoding/utf8_bom.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+
+        assert_eq!(srt.subtitles.len(), 2);
+        assert_eq!(srt.subtitles[0].index, 1);
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:01,000");
+        assert_eq!(srt.subtitles[0].text, "First cue");
+        assert!(srt.warnings().is_empty());
+
+        let reader = std::io::BufReader::new(File::open(test_file_path).unwrap());
+        let subtitles: Vec<Subtitle> = SRT::parse_iter(reader).map(Result::unwrap).collect();
+        assert_eq!(subtitles.len(), 2);
+        assert_eq!(subtitles[0].index, 1);
+        assert_eq!(subtitles[0].text, "First cue");
+    }
+
+    #[test]
+    fn test_srt_read_file_with_encoding() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/encoding/windows_1256.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file_with_encoding("windows-1256").unwrap();
+        assert_eq!(srt.subtitles[0].text, "مرحبا بكم");
+
+        let mut srt = SRT::new(test_file_path);
+        assert!(srt.read_file_with_encoding("klingon").is_err());
+    }
+
+    #[test]
+    fn test_srt_write_to_crlf() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First\nline"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+        ];
+        srt.write_options.line_ending = LineEnding::Crlf;
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1\r\n00:00:01,000 --> 00:00:02,000\r\nFirst\r\nline\r\n\r\n\
+             2\r\n00:00:03,000 --> 00:00:04,000\r\nSecond\r\n"
+        );
+    }
+
+    #[test]
+    fn test_srt_write_to_without_indices() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+        ];
+        srt.write_options.indices = false;
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "00:00:01,000 --> 00:00:02,000\nFirst\n\n00:00:03,000 --> 00:00:04,000\nSecond\n"
+        );
+
+        srt.write_options.blank_separator = false;
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "00:00:01,000 --> 00:00:02,000\nFirst\n00:00:03,000 --> 00:00:04,000\nSecond\n"
+        );
+    }
+
+    #[test]
+    fn test_srt_write_to_without_blank_separator() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+        ];
+        srt.write_options.blank_separator = false;
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1\n00:00:01,000 --> 00:00:02,000\nFirst\n2\n00:00:03,000 --> 00:00:04,000\nSecond\n"
+        );
+    }
+
+    #[test]
+    fn test_srt_read_file_strips_carriage_returns() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/encoding/windows_1256.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+        assert!(srt.subtitles.iter().all(|s| !s.text.contains('\r')));
+    }
+
+    #[test]
+    fn test_srt_sort() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:05,000", "00:00:06,000", "Third"),
+            subtitle("00:00:01,000", "00:00:03,000", "Second"),
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+        ];
+
+        srt.sort();
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["First", "Second", "Third"]);
+    }
+
+    #[test]
+    fn test_srt_renumber() {
+        let mut srt = SRT::new("test.srt");
+        srt.parse(
+            "3\n00:00:05,000 --> 00:00:06,000\nThird\n\n\
+             1\n00:00:01,000 --> 00:00:02,000\nFirst\n\n\
+             7\n00:00:03,000 --> 00:00:04,000\nSecond\n",
+        )
+        .unwrap();
+        let indices: Vec<usize> = srt.subtitles.iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![3, 1, 7]);
+
+        srt.sort();
+        srt.renumber();
+
+        let indices: Vec<usize> = srt.subtitles.iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["First", "Second", "Third"]);
+    }
+
+    #[test]
+    fn test_srt_is_sorted() {
+        let content = "1\n00:00:05,000 --> 00:00:06,000\nSecond\n\n\
+                       2\n00:00:01,000 --> 00:00:02,000\nFirst\n";
+
+        let mut srt = SRT::new("test.srt");
+        srt.parse(content).unwrap();
+        assert!(!srt.is_sorted());
+        assert_eq!(srt.warnings(), ["Subtitles are not in chronological order"]);
+        assert_eq!(srt.subtitles[0].text, "Second");
+
+        let mut srt = SRT::new("test.srt");
+        srt.parse_options.auto_sort = true;
+        srt.parse(content).unwrap();
+        assert!(srt.is_sorted());
+        assert_eq!(srt.subtitles[0].text, "First");
+    }
+
+    #[test]
+    fn test_srt_find_overlaps() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:03,000", "First"),
+            subtitle("00:00:02,000", "00:00:04,000", "Second"),
+            subtitle("00:00:04,000", "00:00:05,000", "Third"),
+        ];
+
+        assert_eq!(srt.find_overlaps(), vec![0]);
+    }
+
+    #[test]
+    fn test_srt_append() {
+        let mut part1 = SRT::new("part1.srt");
+        part1.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "One"),
+            subtitle("00:00:03,000", "00:00:04,000", "Two"),
+        ];
+        let mut part2 = SRT::new("part2.srt");
+        part2.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Three"),
+            subtitle("00:00:03,000", "00:00:04,000", "Four"),
+        ];
+
+        part1.append(&part2, Duration::from_secs(60));
+
+        assert_eq!(part1.subtitles.len(), 4);
+        assert_eq!(part1.subtitles[1].text, "Two");
+        assert_eq!(part1.subtitles[2].text, "Three");
+        assert_eq!(part1.subtitles[2].start_time.to_string(), "00:01:01,000");
+        assert_eq!(part1.subtitles[3].end_time.to_string(), "00:01:04,000");
+        assert!(part1.find_overlaps().is_empty());
+        // The appended SRT is left untouched
+        assert_eq!(part2.subtitles[0].start_time.to_string(), "00:00:01,000");
+        let indices: Vec<usize> = part1.subtitles.iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_srt_append_drops_invalid_incoming() {
+        let mut part1 = SRT::new("part1.srt");
+        part1.parse_options.validation.reject_punctuation_only = false;
+        part1.subtitles = vec![subtitle("00:00:01,000", "00:00:02,000", "...")];
+        let mut part2 = SRT::new("part2.srt");
+        part2.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", ""),
+            subtitle("00:00:03,000", "00:00:04,000", "Kept"),
+        ];
+
+        part1.append(&part2, Duration::from_secs(60));
+
+        let texts: Vec<&str> = part1.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["...", "Kept"]);
+        assert_eq!(part1.dropped(), 1);
+    }
+
+    #[test]
+    fn test_srt_split_at() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:03,000", "One"),
+            subtitle("00:00:04,000", "00:00:06,000", "Two"),
+            subtitle("00:00:09,000", "00:00:12,000", "Three"),
+            subtitle("00:00:13,000", "00:00:15,000", "Four"),
+        ];
+
+        let (first, second) = srt.split_at(Timestamp::from_string("00:00:10,000").unwrap());
+
+        assert_eq!(first.subtitles.len(), 3);
+        assert_eq!(first.subtitles[1].end_time.to_string(), "00:00:06,000");
+        assert_eq!(first.subtitles[2].start_time.to_string(), "00:00:09,000");
+        assert_eq!(first.subtitles[2].end_time.to_string(), "00:00:10,000");
+
+        assert_eq!(second.subtitles.len(), 1);
+        assert_eq!(second.subtitles[0].text, "Four");
+        assert_eq!(second.subtitles[0].start_time.to_string(), "00:00:03,000");
+        assert_eq!(second.subtitles[0].end_time.to_string(), "00:00:05,000");
+    }
+
+    #[test]
+    fn test_srt_split_at_cue_start() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:03,000", "One"),
+            subtitle("00:00:04,000", "00:00:04,000", "Zero-length"),
+            subtitle("00:00:04,000", "00:00:06,000", "Two"),
+        ];
+
+        let (first, second) = srt.split_at(Timestamp::from_string("00:00:04,000").unwrap());
+
+        assert_eq!(first.subtitles.len(), 1);
+        assert_eq!(first.subtitles[0].text, "One");
+        let texts: Vec<&str> = second.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Zero-length", "Two"]);
+        assert_eq!(second.subtitles[1].start_time.to_string(), "00:00:00,000");
+        assert_eq!(second.subtitles[1].end_time.to_string(), "00:00:02,000");
+    }
+
+    #[test]
+    fn test_srt_slice() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:05,000", "Before"),
+            subtitle("00:00:08,000", "00:00:11,000", "Straddles start"),
+            subtitle("00:00:12,000", "00:00:15,000", "Inside"),
+            subtitle("00:00:19,000", "00:00:22,000", "Straddles end"),
+            subtitle("00:00:20,000", "00:00:25,000", "After"),
+        ];
+
+        let slice = srt.slice(
+            Timestamp::from_string("00:00:10,000").unwrap(),
+            Timestamp::from_string("00:00:20,000").unwrap(),
+        );
+
+        let timings: Vec<(String, String)> = slice
+            .subtitles
+            .iter()
+            .map(|s| (s.start_time.to_string(), s.end_time.to_string()))
+            .collect();
+        assert_eq!(
+            timings,
+            vec![
+                ("00:00:00,000".to_string(), "00:00:01,000".to_string()),
+                ("00:00:02,000".to_string(), "00:00:05,000".to_string()),
+                ("00:00:09,000".to_string(), "00:00:10,000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_srt_read_file_reports_block_number() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parse_errors/broken_block.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.parse_options.mode = ParseMode::Strict;
+        let error = srt.read_file().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Subtitle parse error: block 3: Invalid seconds format"
+        );
+    }
+
+    #[test]
+    fn test_srt_read_file_lenient() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parse_errors/broken_block.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["First", "Second", "Fourth"]);
+        assert_eq!(srt.warnings(), ["block 3: Invalid seconds format"]);
+        assert_eq!(srt.dropped(), 1);
+    }
+
+    #[test]
+    fn test_srt_read_file_blank_line_in_text() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/blank_lines/inner_blank.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["First line", "Second"]);
+        assert_eq!(srt.warnings(), ["block 2: No timestamp found"]);
+
+        let mut srt = SRT::new(test_file_path);
+        srt.parse_options.blank_lines = BlankLineMode::BeforeIndex;
+        srt.read_file().unwrap();
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["First line\n\nstill the first subtitle", "Second"]
+        );
+        assert!(srt.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_srt_parse_lenient_timestamps() {
+        let content = "1\n00:00:01.000 --> 00:00:02,500\nMixed\n";
+
+        let mut srt = SRT::new("test.srt");
+        srt.parse(content).unwrap();
+        assert!(srt.subtitles.is_empty());
+
+        let mut srt = SRT::new("test.srt");
+        srt.parse_options.lenient_timestamps = true;
+        srt.parse(content).unwrap();
+        assert_eq!(srt.subtitles.len(), 1);
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:01,000");
+    }
+
+    #[test]
+    fn test_srt_parse_trim_text() {
+        let content = "1\n00:00:01,000 --> 00:00:02,000\n   Indented line  \n\tTabbed line\n";
+
+        let mut srt = SRT::new("test.srt");
+        srt.parse(content).unwrap();
+        assert_eq!(srt.subtitles[0].text, "Indented line\nTabbed line");
+
+        let mut srt = SRT::new("test.srt");
+        srt.parse_options.trim_text = false;
+        srt.parse(content).unwrap();
+        assert_eq!(srt.subtitles[0].text, "   Indented line  \n\tTabbed line");
+    }
+
+    #[test]
+    fn test_srt_parse_max_block_lines() {
+        let mut content = "1\n00:00:01,000 --> 00:00:02,000\nFirst\n\n".to_string();
+        content.push_str("2\n00:00:03,000 --> 00:00:04,000\n");
+        content.push_str(&"No separator in sight\n".repeat(150));
+
+        let mut srt = SRT::new("test.srt");
+        let error = srt.parse(&content).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Subtitle parse error: Block starting at line 5 exceeds 100 lines"
+        );
+
+        let mut srt = SRT::new("test.srt");
+        srt.parse_options.max_block_lines = 200;
+        srt.parse(&content).unwrap();
+        assert_eq!(srt.subtitles.len(), 2);
+    }
+
+    #[test]
+    fn test_srt_read_file_multiple_lines() {
+        let mut srt = SRT::new("test.srt");
+        srt.parse("1\n00:00:01,000 --> 00:00:02,000\nFirst\nline\n\n2\n00:00:03,000 --> 00:00:04,000\nSecond\n")
+            .unwrap();
+
+        assert_eq!(srt.subtitles.len(), 2);
+        assert_eq!(srt.subtitles[0].text, "First\nline");
+        assert_eq!(srt.subtitles[1].text, "Second");
+    }
+
+    #[test]
+    fn test_srt_read_file_missing_separators() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/srt_loader/test1.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+
+        assert_eq!(srt.subtitles.len(), 2);
+        assert_eq!(srt.subtitles[0].text, "Hello, world!");
+        assert_eq!(srt.subtitles[1].text, "This is a test.");
+    }
+
+    #[test]
+    fn test_srt_stats() {
+        let test_file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/stats/input.srt");
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+
+        let stats = srt.stats();
+        assert_eq!(stats.cue_count, 3);
+        assert_eq!(stats.total_on_screen, Duration::from_secs(7));
+        assert_eq!(stats.total_gap, Duration::from_secs(2));
+        assert_eq!(stats.longest, Duration::from_secs(4));
+        assert_eq!(stats.shortest, Duration::from_secs(1));
+        assert!((stats.average_cps - 25.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_srt_find_fast() {
+        let srt = SRT {
+            file_path: "test.srt".to_string(),
+            subtitles: vec![
+                Subtitle {
+                    index: 1,
+                    start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+                    end_time: Timestamp::from_string("00:00:05,000").unwrap(),
+                    text: "A comfortable line.".to_string(),
+                    settings: None,
+                },
+                Subtitle {
+                    index: 2,
+                    start_time: Timestamp::from_string("00:00:05,000").unwrap(),
+                    end_time: Timestamp::from_string("00:00:06,000").unwrap(),
+                    text: "This line is far too long to read in a single second.".to_string(),
+                    settings: None,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(srt.find_fast(17.0), vec![1]);
+        assert!(srt.find_fast(100.0).is_empty());
+    }
+
+    #[test]
+    fn test_srt_enforce_min_duration() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:01,200", "Extended"),
+            subtitle("00:00:03,000", "00:00:03,100", "Capped"),
+            subtitle("00:00:03,500", "00:00:03,600", "Last"),
+        ];
+
+        srt.enforce_min_duration(Duration::from_secs(1));
+
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:02,000");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:03,499");
+        assert_eq!(srt.subtitles[2].end_time.to_string(), "00:00:04,500");
+    }
+
+    #[test]
+    fn test_srt_gaps() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:05,000", "00:00:06,000", "Second"),
+            subtitle("00:00:06,000", "00:00:07,000", "Third"),
+            subtitle("00:00:06,500", "00:00:08,000", "Fourth"),
+        ];
+
+        assert_eq!(srt.gaps(), vec![(0, Duration::from_secs(3))]);
+    }
+
+    #[test]
+    fn test_srt_strip_tags() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "<i>Italic</i> text"),
+            subtitle(
+                "00:00:03,000",
+                "00:00:04,000",
+                "<font color=\"red\">Red</font>",
+            ),
+        ];
+
+        srt.strip_tags();
+
+        assert_eq!(srt.subtitles[0].text, "Italic text");
+        assert_eq!(srt.subtitles[1].text, "Red");
+    }
+
+    #[test]
+    fn test_srt_trim_empty() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", ""),
+            subtitle("00:00:03,000", "00:00:04,000", "  "),
+            subtitle("00:00:05,000", "00:00:06,000", "Valid"),
+            subtitle("00:00:07,000", "00:00:08,000", ""),
+            subtitle("00:00:09,000", "00:00:10,000", "Also valid"),
+            subtitle("00:00:11,000", "00:00:12,000", "\n"),
+        ];
+
+        srt.trim_empty(false);
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Valid", "", "Also valid"]);
+
+        srt.trim_empty(true);
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Valid", "Also valid"]);
+    }
+
+    #[test]
+    fn test_srt_trim_empty_all_empty() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", ""),
+            subtitle("00:00:03,000", "00:00:04,000", " "),
+        ];
+
+        srt.trim_empty(false);
+        assert!(srt.subtitles.is_empty());
+    }
+
+    #[test]
+    fn test_srt_remove_sound_cues() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "♪ Theme song ♪"),
+            subtitle("00:00:03,000", "00:00:04,000", "[door creaks] Hello?"),
+        ];
+
+        srt.remove_sound_cues();
+
+        assert_eq!(srt.subtitles.len(), 1);
+        assert_eq!(srt.subtitles[0].text, "Hello?");
+    }
+
+    #[test]
+    fn test_srt_retime() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:10,000", "00:00:12,000", "First"),
+            subtitle("00:01:00,000", "00:01:02,000", "Middle"),
+            subtitle("00:01:50,000", "00:01:52,000", "Last"),
+        ];
+
+        srt.retime(
+            Timestamp::from_string("00:00:10,000").unwrap(),
+            Timestamp::from_string("00:00:11,000").unwrap(),
+            Timestamp::from_string("00:01:50,000").unwrap(),
+            Timestamp::from_string("00:02:01,000").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:11,000");
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:01:06,000");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:01:08,200");
+        assert_eq!(srt.subtitles[2].start_time.to_string(), "00:02:01,000");
+    }
+
+    #[test]
+    fn test_srt_retime_same_sync_points() {
+        let mut srt = SRT::new("test.srt");
+        let ts = Timestamp::from_string("00:00:10,000").unwrap();
+        assert!(srt.retime(ts.clone(), ts.clone(), ts.clone(), ts).is_err());
+    }
+
+    #[test]
+    fn test_srt_convert_framerate() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("01:00:00,000", "01:00:02,000", "Near the end"),
+        ];
+
+        srt.convert_framerate(25.0, 23.976).unwrap();
+
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:01,043");
+        // 3,600,000 ms * 25 / 23.976 = 3,753,753.75 ms
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "01:02:33,754");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "01:02:35,839");
+    }
+
+    #[test]
+    fn test_srt_convert_framerate_invalid() {
+        let mut srt = SRT::new("test.srt");
+        assert!(srt.convert_framerate(0.0, 25.0).is_err());
+        assert!(srt.convert_framerate(25.0, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_srt_scale() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,500", "First"),
+            subtitle("00:00:40,000", "00:00:45,250", "Second"),
+        ];
+
+        srt.scale(2.0).unwrap();
+
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:02,000");
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:05,000");
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:01:20,000");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:01:30,500");
+    }
+
+    #[test]
+    fn test_srt_scale_invalid() {
+        let mut srt = SRT::new("test.srt");
+        assert!(srt.scale(0.0).is_err());
+        assert!(srt.scale(-1.0).is_err());
+        assert!(srt.scale(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_srt_wrap_all() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Short"),
+            subtitle("00:00:03,000", "00:00:04,000", "Long enough to wrap"),
+        ];
+
+        srt.wrap_all(10);
+
+        assert_eq!(srt.subtitles[0].text, "Short");
+        assert_eq!(srt.subtitles[1].text, "Long\nenough to\nwrap");
+    }
+
+    #[test]
+    fn test_srt_unwrap_all() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "One\ntwo\nthree"),
+            subtitle("00:00:03,000", "00:00:04,000", "Single"),
+        ];
+
+        srt.unwrap_all();
+
+        assert_eq!(srt.subtitles[0].text, "One two three");
+        assert_eq!(srt.subtitles[1].text, "Single");
+    }
+
+    #[test]
+    fn test_srt_change_case() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Hello <b>World</b>"),
+            subtitle("00:00:03,000", "00:00:04,000", "Ça va? نعم"),
+        ];
+
+        srt.to_uppercase();
+        assert_eq!(srt.subtitles[0].text, "HELLO <b>WORLD</b>");
+        assert_eq!(srt.subtitles[1].text, "ÇA VA? نعم");
+
+        srt.to_lowercase();
+        assert_eq!(srt.subtitles[0].text, "hello <b>world</b>");
+        assert_eq!(srt.subtitles[1].text, "ça va? نعم");
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:00:03,000");
+    }
+
+    #[test]
+    fn test_srt_clamp_duration() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:11,000", "Ten seconds"),
+            subtitle("00:00:12,000", "00:00:15,000", "Three seconds"),
+        ];
+
+        srt.clamp_duration(Duration::from_secs(6));
+
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:01,000");
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:07,000");
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:00:12,000");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:15,000");
+    }
+
+    #[test]
+    fn test_srt_total_span() {
+        let mut srt = SRT::new("test.srt");
+        assert_eq!(srt.total_span(), None);
+
+        srt.subtitles = vec![
+            subtitle("00:00:05,000", "00:00:30,000", "Longest"),
+            subtitle("00:00:02,500", "00:00:04,000", "Earliest"),
+            subtitle("00:00:10,000", "00:00:12,000", "Middle"),
+        ];
+
+        assert_eq!(srt.total_span(), Some(Duration::from_millis(27500)));
+    }
+
+    #[test]
+    fn test_srt_merge_identical_adjacent() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Run!"),
+            subtitle("00:00:02,200", "00:00:03,000", "Run!"),
+            subtitle("00:00:03,000", "00:00:04,000", "Hide!"),
+            subtitle("00:00:10,000", "00:00:11,000", "Hide!"),
+        ];
+
+        srt.merge_identical_adjacent(&MergeOptions {
+            max_gap: Some(Duration::from_secs(1)),
+            ..Default::default()
+        });
+
+        let timings: Vec<(String, String, &str)> = srt
+            .subtitles
+            .iter()
+            .map(|s| {
+                (
+                    s.start_time.to_string(),
+                    s.end_time.to_string(),
+                    s.text.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            timings,
+            vec![
+                (
+                    "00:00:01,000".to_string(),
+                    "00:00:03,000".to_string(),
+                    "Run!"
+                ),
+                (
+                    "00:00:03,000".to_string(),
+                    "00:00:04,000".to_string(),
+                    "Hide!"
+                ),
+                (
+                    "00:00:10,000".to_string(),
+                    "00:00:11,000".to_string(),
+                    "Hide!"
+                ),
+            ]
+        );
+
+        srt.merge_identical_adjacent(&MergeOptions::default());
+        assert_eq!(srt.subtitles.len(), 2);
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:11,000");
+    }
+
+    #[test]
+    fn test_srt_write_vtt_to() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,500", "First\nline"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+        ];
+
+        let mut output = Vec::new();
+        let written = srt.write_vtt_to(&mut output).unwrap();
+
+        assert_eq!(written, 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.500\nFirst\nline\n\n00:00:03.000 --> 00:00:04.000\nSecond\n"
+        );
+    }
+
+    #[test]
+    fn test_srt_write_ass_to() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,005", "00:00:02,500", "First\nline"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second, with a comma"),
+        ];
+
+        let mut output = Vec::new();
+        let written = srt.write_ass_to(&mut output).unwrap();
+
+        assert_eq!(written, 2);
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output,
+            "[Script Info]\nScriptType: v4.00+\n\n[Events]\n\
+             Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+             Dialogue: 0,0:00:01.01,0:00:02.50,Default,,0,0,0,,First\\Nline\n\
+             Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,Second, with a comma\n"
+        );
+
+        let mut read = SRT::new("test.ass");
+        read.parse_ass(&output).unwrap();
+        let texts: Vec<&str> = read.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["First\nline", "Second, with a comma"]);
+    }
+
+    #[test]
+    fn test_srt_write_ass() {
+        let output_path = std::env::temp_dir().join(format!(
+            "shu-2al_test_srt_write_ass_{}.ass",
+            std::process::id()
+        ));
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![subtitle("00:00:01,005", "00:00:02,500", "Hello")];
+
+        let written = srt.write_ass(output_path.to_str().unwrap());
+        let mut read = SRT::new(output_path.to_str().unwrap());
+        let read_result = read.read_ass();
+        std::fs::remove_file(&output_path).unwrap();
+
+        assert_eq!(written, Ok(1));
+        assert!(read_result.is_ok());
+        assert_eq!(read.subtitles.len(), 1);
+        assert_eq!(read.subtitles[0].start_time.to_string(), "00:00:01,010");
+    }
+
+    #[test]
+    fn test_srt_clean() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            Subtitle {
+                index: 1,
+                ..subtitle("00:00:01,000", "00:00:02,000", "Hello")
+            },
+            Subtitle {
+                index: 2,
+                ..subtitle("00:00:03,000", "00:00:04,000", "اشتركوا في القناة")
+            },
+            Subtitle {
+                index: 3,
+                ..subtitle("00:00:05,000", "00:00:06,000", "...")
+            },
+        ];
+
+        let removed = srt.clean();
+
+        assert_eq!(srt.subtitles.len(), 1);
+        assert_eq!(srt.subtitles[0].text, "Hello");
+        let removed: Vec<(usize, &str)> = removed
+            .iter()
+            .map(|(subtitle, reason)| (subtitle.index, reason.as_str()))
+            .collect();
+        assert_eq!(
+            removed,
+            vec![
+                (2, "Contains banned phrase \"شتركوا في القناة\""),
+                (3, "Punctuation only"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_srt_snap_to_frames() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![subtitle("00:00:01,010", "00:00:02,030", "Hello")];
+
+        srt.snap_to_frames(25.0);
+
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:01,000");
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:02,040");
+    }
+
+    #[test]
+    fn test_srt_parse_iter() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parse_errors/broken_block.srt"
+        );
+        let file = File::open(test_file_path).unwrap();
+
+        let results: Vec<Result<Subtitle, String>> =
+            SRT::parse_iter(std::io::BufReader::new(file)).collect();
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 3);
+        assert_eq!(
+            results[2].as_ref().unwrap_err(),
+            "block 3: Invalid seconds format"
+        );
+    }
+
+    #[test]
+    fn test_srt_validate() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:02,000", "00:00:01,000", "Reversed"),
+            subtitle("00:00:03,000", "00:00:05,000", "Overlapping"),
+            subtitle("00:00:04,000", "00:00:06,000", "Overlapped"),
+            subtitle("00:00:08,000", "00:00:07,000", "Reversed again"),
+        ];
+
+        assert_eq!(
+            srt.validate(),
+            Err(vec![
+                "Subtitle 1: End time before start time".to_string(),
+                "Subtitle 4: End time before start time".to_string(),
+            ])
+        );
+
+        srt.write_options.reject_overlaps = true;
+        assert_eq!(srt.validate().unwrap_err().len(), 3);
+
+        srt.write_options.strict = true;
+        assert!(srt.write_to(Vec::new()).is_err());
+
+        srt.subtitles.truncate(0);
+        assert_eq!(srt.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_srt_set_gap() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:02,100", "00:00:03,100", "Second"),
+            subtitle("00:00:05,000", "00:00:07,000", "Third"),
+        ];
+
+        srt.set_gap(Duration::from_millis(500));
+
+        let timings: Vec<(String, String)> = srt
+            .subtitles
+            .iter()
+            .map(|s| (s.start_time.to_string(), s.end_time.to_string()))
+            .collect();
+        assert_eq!(
+            timings,
+            vec![
+                ("00:00:01,000".to_string(), "00:00:02,000".to_string()),
+                ("00:00:02,500".to_string(), "00:00:03,500".to_string()),
+                ("00:00:04,000".to_string(), "00:00:06,000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_srt_shift_range() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+            subtitle("00:00:05,000", "00:00:06,000", "Third"),
+            subtitle("00:00:07,000", "00:00:08,000", "Fourth"),
+        ];
+        let original = srt.subtitles.clone();
+
+        srt.shift_range(1, 2, Duration::from_millis(500), Direction::Forward)
+            .unwrap();
+
+        assert_eq!(srt.subtitles[0], original[0]);
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:00:03,500");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:04,500");
+        assert_eq!(srt.subtitles[2].start_time.to_string(), "00:00:05,500");
+        assert_eq!(srt.subtitles[2].end_time.to_string(), "00:00:06,500");
+        assert_eq!(srt.subtitles[3], original[3]);
+
+        assert!(srt
+            .shift_range(2, 4, Duration::from_millis(500), Direction::Forward)
+            .is_err());
+        assert!(srt
+            .shift_range(2, 1, Duration::from_millis(500), Direction::Backward)
+            .is_err());
+    }
+
+    #[test]
+    fn test_srt_replace_text() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Teh cat"),
+            subtitle("00:00:03,000", "00:00:04,000", "The dog"),
+            subtitle("00:00:05,000", "00:00:06,000", "Teh end of teh story"),
+        ];
+        let original = srt.subtitles.clone();
+
+        assert_eq!(srt.replace_text("Teh", "The"), 2);
+        assert_eq!(srt.replace_text("teh", "the"), 1);
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["The cat", "The dog", "The end of the story"]);
+        assert!(srt
+            .subtitles
+            .iter()
+            .zip(&original)
+            .all(|(a, b)| a.start_time == b.start_time && a.end_time == b.end_time));
+        assert_eq!(srt.replace_text("", "x"), 0);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_srt_replace_regex() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Teh cat"),
+            subtitle("00:00:03,000", "00:00:04,000", "The dog"),
+            subtitle("00:00:05,000", "00:00:06,000", "teh end"),
+        ];
+
+        let pattern = regex::Regex::new(r"\b([Tt])eh\b").unwrap();
+        assert_eq!(srt.replace_regex(&pattern, "${1}he"), 2);
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["The cat", "The dog", "the end"]);
+    }
+
+    #[test]
+    fn test_srt_count_matching() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "the cat"),
+            subtitle("00:00:03,000", "00:00:04,000", "The dog"),
+            subtitle("00:00:05,000", "00:00:06,000", "over there"),
+            subtitle("00:00:07,000", "00:00:09,000", "Goodbye"),
+        ];
+
+        assert_eq!(srt.count_containing("the"), 2);
+        assert_eq!(
+            srt.count_matching(|s| s.duration() > Duration::from_secs(1)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_srt_find_tight_gaps() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Tight"),
+            subtitle("00:00:02,040", "00:00:03,000", "Comfortable"),
+            subtitle("00:00:04,000", "00:00:05,000", "Abutting"),
+            subtitle("00:00:05,000", "00:00:06,000", "Last"),
+        ];
+
+        assert_eq!(srt.find_tight_gaps(Duration::from_millis(80)), vec![0]);
+    }
+
+    #[test]
+    fn test_srt_normalize() {
+        let input_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/normalize/input.srt"
+        );
+        let expected_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/normalize/expected.srt"
+        );
+        let mut srt = SRT::new(input_path);
+        srt.parse_options.blank_lines = BlankLineMode::BeforeIndex;
+        srt.write_options.keep_blank_lines = true;
+        srt.read_file().unwrap();
+
+        srt.normalize();
+
+        let indices: Vec<usize> = srt.subtitles.iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        assert_eq!(output, std::fs::read(expected_path).unwrap());
+    }
+
+    #[test]
+    fn test_srt_merge_same_time() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "- Who's there?"),
+            subtitle("00:00:01,000", "00:00:02,000", "- Me."),
+            subtitle("00:00:01,000", "00:00:03,000", "Longer"),
+        ];
+
+        srt.merge_same_time(&MergeOptions::default());
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["- Who's there?\n- Me.", "Longer"]);
+    }
+
+    #[test]
+    fn test_srt_merge_close_space_separator() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "I think"),
+            subtitle("00:00:02,100", "00:00:03,000", "therefore"),
+            subtitle("00:00:03,000", "00:00:04,000", "I am."),
+            subtitle("00:00:06,000", "00:00:07,000", "Next"),
+        ];
+        let options = MergeOptions {
+            max_gap: Some(Duration::from_millis(500)),
+            join_separator: " ".to_string(),
+        };
+
+        srt.merge_close(&options);
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["I think therefore I am.", "Next"]);
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:04,000");
+    }
+
+    #[test]
+    fn test_srt_merge_close_default_options() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "One"),
+            subtitle("00:00:02,000", "00:00:03,000", "Two"),
+            subtitle("00:00:03,001", "00:00:04,000", "Three"),
+            subtitle("00:00:06,000", "00:00:07,000", "Four"),
+        ];
+
+        srt.merge_close(&MergeOptions::default());
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["One\nTwo", "Three", "Four"]);
+    }
+
+    #[test]
+    fn test_srt_to_transcript() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/transcript/input.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+
+        assert_eq!(
+            srt.to_transcript(false),
+            "Where are you going\nat this hour?\n\nHome."
+        );
+        assert_eq!(
+            srt.to_transcript(true),
+            "Where are you going at this hour?\n\nHome."
+        );
+    }
+
+    #[test]
+    fn test_srt_write_to_strict_long_timestamps() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![Subtitle {
+            end_time: Timestamp::from_millis(360_000_000),
+            ..subtitle("99:59:59,000", "00:00:00,000", "Long")
+        }];
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("99:59:59,000 --> 100:00:00,000"));
+
+        srt.write_options.strict = true;
+        assert_eq!(
+            srt.write_to(Vec::new()).unwrap_err(),
+            "Subtitle 1: Timestamp exceeds 99 hours"
+        );
+    }
+
+    #[test]
+    fn test_srt_shift_all() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+        ];
+        let original = srt.subtitles.clone();
+
+        let error = srt
+            .shift_all(
+                Duration::from_millis(1500),
+                Direction::Backward,
+                ClampMode::Error,
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Time error: Subtitle 1 would be moved before 00:00:00,000"
+        );
+        assert_eq!(srt.subtitles, original);
+
+        srt.shift_all(
+            Duration::from_millis(1500),
+            Direction::Backward,
+            ClampMode::Saturate,
+        )
+        .unwrap();
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:00,000");
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:00,500");
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:00:01,500");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_srt_gzip_round_trip() {
+        let input_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/test_3/input.srt");
+        let output_path = std::env::temp_dir().join(format!(
+            "shu-2al_test_srt_gzip_round_trip_{}.srt.gz",
+            std::process::id()
+        ));
+        let mut srt = SRT::new(input_path);
+        srt.read_file().unwrap();
+
+        srt.write_file(output_path.to_str().unwrap()).unwrap();
+        let compressed = std::fs::read(&output_path).unwrap();
+        let mut read_back = SRT::new(output_path.to_str().unwrap());
+        let result = read_back.read_file();
+        std::fs::remove_file(&output_path).unwrap();
+
+        // gzip magic number
+        assert_eq!(compressed[..2], [0x1f, 0x8b]);
+        result.unwrap();
+        assert_eq!(read_back.subtitles, srt.subtitles);
+    }
+
+    #[test]
+    fn test_srt_prepend_cue() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:02,000", "00:00:03,000", "First"),
+            subtitle("00:00:04,000", "00:00:05,000", "Second"),
+        ];
+
+        srt.prepend_cue("Subtitles by the team".to_string(), Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(srt.subtitles.len(), 3);
+        assert_eq!(srt.subtitles[0].text, "Subtitles by the team");
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:00,000");
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:02,000");
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:00:02,000");
+        let indices: Vec<usize> = srt.subtitles.iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+
+        assert!(srt
+            .prepend_cue("Again".to_string(), Duration::from_secs(1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_srt_find_suspicious() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "It costs 42 dollars."),
+            subtitle("00:00:03,000", "00:00:04,000", "42"),
+            subtitle(
+                "00:00:05,000",
+                "00:00:06,000",
+                "7\n00:00:07,000 --> 00:00:08,000",
+            ),
+            subtitle("00:00:09,000", "00:00:10,000", "00:00:09,500"),
+            subtitle("00:00:11,000", "00:00:12,000", "1984"),
+        ];
+
+        assert_eq!(srt.find_suspicious(), vec![1, 2, 3, 4]);
+
+        srt.subtitles.truncate(1);
+        assert!(srt.find_suspicious().is_empty());
+    }
+
+    #[test]
+    fn test_srt_find_too_many_lines() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "One line"),
+            subtitle("00:00:03,000", "00:00:04,000", "One\nTwo\nThree"),
+            subtitle("00:00:05,000", "00:00:06,000", "One\nTwo"),
+        ];
+
+        assert_eq!(srt.find_too_many_lines(2), vec![1]);
+    }
+
+    #[test]
+    fn test_srt_find_long_lines() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Short\nlines"),
+            subtitle(
+                "00:00:03,000",
+                "00:00:04,000",
+                "Short\nهذا السطر أطول بكثير من الحد المسموح",
+            ),
+        ];
+
+        assert_eq!(srt.find_long_lines(20), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_srt_diff() {
+        let mut original = SRT::new("original.srt");
+        original.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Hello"),
+            subtitle("00:00:03,000", "00:00:04,000", "..."),
+            subtitle("00:00:05,000", "00:00:06,000", "General  Kenobi"),
+            subtitle("00:00:07,000", "00:00:08,000", "Bye"),
+        ];
+        original.renumber();
+
+        let mut cleaned = SRT::new("cleaned.srt");
+        cleaned.subtitles = original.subtitles.clone();
+        cleaned.clean();
+        cleaned.replace_text("  ", " ");
+        cleaned
+            .shift_range(2, 2, Duration::from_millis(500), Direction::Forward)
+            .unwrap();
+        cleaned
+            .subtitles
+            .push(subtitle("00:00:09,000", "00:00:10,000", "The end"));
+        cleaned.renumber();
+
+        let diff = original.diff(&cleaned);
+
+        assert_eq!(
+            diff,
+            vec![
+                DiffEntry::Removed(original.subtitles[1].clone()),
+                DiffEntry::Modified {
+                    before: original.subtitles[2].clone(),
+                    after: cleaned.subtitles[1].clone(),
+                    time_changed: false,
+                    text_changed: true,
+                },
+                DiffEntry::Modified {
+                    before: original.subtitles[3].clone(),
+                    after: cleaned.subtitles[2].clone(),
+                    time_changed: true,
+                    text_changed: false,
+                },
+                DiffEntry::Added(cleaned.subtitles[3].clone()),
+            ]
+        );
+        assert!(original.diff(&original).is_empty());
+    }
+
+    #[test]
+    fn test_srt_find_internal_blanks() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Line one\nLine two"),
+            subtitle("00:00:03,000", "00:00:04,000", "Line one\n\nLine three"),
+        ];
+
+        assert_eq!(srt.find_internal_blanks(), vec![1]);
+    }
+
+    #[test]
+    fn test_srt_read_ass() {
+        let test_file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/ass/input.ass");
+        let mut srt = SRT::new(test_file_path);
+        srt.read_ass().unwrap();
+
+        let cues: Vec<(usize, String, String, &str)> = srt
+            .subtitles
+            .iter()
+            .map(|s| {
+                (
+                    s.index,
+                    s.start_time.to_string(),
+                    s.end_time.to_string(),
+                    s.text.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            cues,
+            vec![
+                (
+                    1,
+                    "00:00:01,500".to_string(),
+                    "00:00:03,250".to_string(),
+                    "Hello, world!"
+                ),
+                (
+                    2,
+                    "00:01:02,050".to_string(),
+                    "00:01:04,000".to_string(),
+                    "First line\nSecond line"
+                ),
+            ]
+        );
+        assert!(srt.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_srt_parse_ass_malformed() {
+        let content = "[Events]\nDialogue: 0,0:00:01.00,Default,,0,0,0,,Too few fields\n\
+                       Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Fine\n";
+
+        let mut srt = SRT::new("test.ass");
+        srt.parse_ass(content).unwrap();
+        assert_eq!(srt.subtitles.len(), 1);
+        assert_eq!(srt.warnings().len(), 1);
+        assert!(srt.warnings()[0].starts_with("line 2: "));
+
+        let mut srt = SRT::new("test.ass");
+        srt.parse_options.mode = ParseMode::Strict;
+        assert!(srt.parse_ass(content).is_err());
+    }
+
+    #[test]
+    fn test_srt_parse_ass_bom() {
+        let content = "\u{feff}[Events]\nDialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Hello\n";
+
+        let mut srt = SRT::new("test.ass");
+        srt.parse_ass(content).unwrap();
+        assert_eq!(srt.subtitles.len(), 1);
+        assert_eq!(srt.subtitles[0].text, "Hello");
+    }
+
+    #[test]
+    fn test_srt_content_hash() {
+        let mut first = SRT::new("first.srt");
+        first
+            .parse("1\n00:00:01,000 --> 00:00:02,000\nHello  world\n\n2\n00:00:03,000 --> 00:00:04,000\nBye\n")
+            .unwrap();
+        let mut renumbered = SRT::new("renumbered.srt");
+        renumbered
+            .parse("7\n00:00:01,000 --> 00:00:02,000\n Hello world \n\n8\n00:00:03,000 --> 00:00:04,000\nBye\n")
+            .unwrap();
+        let mut edited = SRT::new("edited.srt");
+        edited
+            .parse("1\n00:00:01,000 --> 00:00:02,000\nHello world\n\n2\n00:00:03,000 --> 00:00:04,500\nBye\n")
+            .unwrap();
+
+        assert_eq!(first.content_hash(), renumbered.content_hash());
+        assert_ne!(first.content_hash(), edited.content_hash());
+        assert_ne!(first.content_hash(), SRT::new("empty.srt").content_hash());
+    }
+
+    #[test]
+    fn test_srt_read_file_no_trailing_newline() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parse_errors/no_trailing_newline.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+
+        assert_eq!(srt.subtitles.len(), 2);
+        assert_eq!(srt.subtitles[1].text, "Last cue without a newline");
+        assert_eq!(srt.dropped(), 0);
+    }
+
+    #[test]
+    fn test_srt_read_file_trailing_notes() {
+        let test_file_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parse_errors/trailing_notes.srt"
+        );
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file().unwrap();
+        assert_eq!(srt.subtitles.len(), 2);
+        assert_eq!(srt.dropped(), 1);
+        assert_eq!(srt.warnings(), ["block 3: No timestamp found"]);
+
+        let mut srt = SRT::new(test_file_path);
+        srt.parse_options.mode = ParseMode::Strict;
+        let error = srt.read_file().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Subtitle parse error: block 3: No timestamp found"
+        );
+    }
+
+    #[test]
+    fn test_srt_clamp_to_length() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:50,000", "00:00:55,000", "Inside"),
+            subtitle("00:00:58,000", "00:01:05,000", "Crossing the end"),
+            subtitle("00:01:00,000", "00:01:02,000", "At the end"),
+            subtitle("00:01:10,000", "00:01:12,000", "Beyond"),
+        ];
+
+        srt.clamp_to_length(Duration::from_secs(60));
+
+        let timings: Vec<(String, String)> = srt
+            .subtitles
+            .iter()
+            .map(|s| (s.start_time.to_string(), s.end_time.to_string()))
+            .collect();
+        assert_eq!(
+            timings,
+            vec![
+                ("00:00:50,000".to_string(), "00:00:55,000".to_string()),
+                ("00:00:58,000".to_string(), "00:01:00,000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_srt_first_order_violation() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+            subtitle("00:00:02,500", "00:00:02,900", "Out of order"),
+            subtitle("00:00:01,500", "00:00:01,900", "Also out of order"),
+        ];
+        assert_eq!(srt.first_order_violation(), Some(2));
+
+        srt.sort();
+        assert_eq!(srt.first_order_violation(), None);
+    }
+
+    #[test]
+    fn test_srt_from_bytes() {
+        let content = "1\r\n00:00:01,000 --> 00:00:02,000\r\nمرحبا\r\n\r\n\
+                       2\r\n00:00:03,000 --> 00:00:04,000\r\nHello\r\n";
+        let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+        with_bom.extend_from_slice(content.as_bytes());
+
+        for bytes in [content.as_bytes(), &with_bom] {
+            let srt = SRT::from_bytes(bytes).unwrap();
+            assert_eq!(srt.file_path, "");
+            assert_eq!(srt.subtitles.len(), 2);
+            assert_eq!(srt.subtitles[0].index, 1);
+            assert_eq!(srt.subtitles[0].text, "مرحبا");
+        }
+    }
+
+    #[test]
+    fn test_srt_snap_starts_to() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle(
+                "00:00:01,900",
+                "00:00:03,000",
+                "Snaps back to the first cut",
+            ),
+            subtitle("00:00:05,000", "00:00:06,000", "Too far from any cut"),
+        ];
+        let cuts = [
+            Timestamp::from_string("00:00:06,000").unwrap(),
+            Timestamp::from_string("00:00:01,800").unwrap(),
+        ];
+
+        srt.snap_starts_to(&cuts, Duration::from_millis(200));
+
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:01,800");
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:02,900");
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:00:05,000");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:06,000");
+    }
+
+    #[test]
+    fn test_srt_append_to_file() {
+        let output_path = std::env::temp_dir().join(format!(
+            "shu-2al_test_srt_append_to_file_{}.srt",
+            std::process::id()
+        ));
+        let output = output_path.to_str().unwrap();
+        let _ = std::fs::remove_file(&output_path);
+
+        let mut first = SRT::new("first.srt");
+        first.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "One"),
+            subtitle("00:00:03,000", "00:00:04,000", "Two"),
+        ];
+        let mut second = SRT::new("second.srt");
+        second.subtitles = vec![subtitle("00:00:05,000", "00:00:06,000", "Three")];
+
+        let next = first.append_to_file(output, 1).unwrap();
+        assert_eq!(next, 3);
+        let next = second.append_to_file(output, next).unwrap();
+        assert_eq!(next, 4);
+
+        let mut appended = SRT::new(output);
+        appended.read_file().unwrap();
+        std::fs::remove_file(&output_path).unwrap();
+        let indices: Vec<usize> = appended.subtitles.iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+        assert!(appended.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_srt_extend_last_to() {
+        let mut srt = SRT::new("test.srt");
+        assert_eq!(
+            srt.extend_last_to(Timestamp::from_millis(30_000)),
+            Err("No subtitles".to_string())
+        );
+
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:03,000", "00:00:05,000", "Thanks for watching"),
+        ];
+        srt.extend_last_to(Timestamp::from_string("00:00:30,000").unwrap())
+            .unwrap();
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:30,000");
+        assert_eq!(srt.subtitles[0].end_time.to_string(), "00:00:02,000");
+
+        assert_eq!(
+            srt.extend_last_to(Timestamp::from_string("00:00:02,000").unwrap()),
+            Err("End time before start time".to_string())
+        );
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:30,000");
+    }
+
+    #[test]
+    fn test_srt_from_parts() {
+        let part1 = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parts/movie.part1.srt"
+        );
+        let part2 = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/parts/movie.part2.srt"
+        );
+
+        let srt = SRT::from_parts(&[part1, part2]).unwrap();
+
+        let cues: Vec<(usize, String, &str)> = srt
+            .subtitles
+            .iter()
+            .map(|s| (s.index, s.start_time.to_string(), s.text.as_str()))
+            .collect();
+        assert_eq!(
+            cues,
+            vec![
+                (1, "00:00:01,000".to_string(), "Part one begins"),
+                (2, "00:00:50,000".to_string(), "Part one ends"),
+                (3, "00:01:02,000".to_string(), "Part two begins"),
+                (4, "00:01:10,000".to_string(), "Part two ends"),
+            ]
+        );
+
+        let error = SRT::from_parts(&[part1, "missing.part2.srt"])
+            .err()
+            .unwrap();
+        assert!(error.starts_with("missing.part2.srt: "));
+    }
+
+    #[test]
+    fn test_srt_read_file_with_progress() {
+        let test_file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/test_1/input.srt");
+        let length = std::fs::metadata(test_file_path).unwrap().len();
+
+        let mut reports = Vec::new();
+        let mut srt = SRT::new(test_file_path);
+        srt.read_file_with_progress(|read| reports.push(read))
+            .unwrap();
+
+        assert!(reports.len() > 1);
+        assert!(reports.is_sorted());
+        assert_eq!(reports.last(), Some(&length));
+
+        let mut expected = SRT::new(test_file_path);
+        expected.read_file().unwrap();
+        assert_eq!(srt.subtitles, expected.subtitles);
+    }
+
+    #[test]
+    fn test_srt_fix_reversed() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Fine"),
+            subtitle("00:00:04,500", "00:00:03,000", "Reversed"),
+            subtitle("00:00:05,000", "00:00:05,000", "Zero length"),
+        ];
+
+        assert_eq!(srt.fix_reversed(), 1);
+
+        assert_eq!(srt.subtitles[1].start_time.to_string(), "00:00:03,000");
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:04,500");
+        assert_eq!(srt.subtitles[0].start_time.to_string(), "00:00:01,000");
+        assert_eq!(srt.fix_reversed(), 0);
+    }
+
+    #[test]
+    fn test_srt_iter_mut() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Hello"),
+            subtitle("00:00:03,000", "00:00:04,000", "Goodbye"),
+        ];
+
+        for subtitle in srt.iter_mut() {
+            subtitle.text = subtitle.text.to_uppercase();
+        }
+        for subtitle in &mut srt {
+            subtitle.end_time = subtitle.end_time.clone() + Duration::from_millis(500);
+        }
+
+        let texts: Vec<&str> = srt.subtitles.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["HELLO", "GOODBYE"]);
+        assert_eq!(srt.subtitles[1].end_time.to_string(), "00:00:04,500");
+    }
+
+    #[test]
+    fn test_srt_write_to_index_start_and_step() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "First"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+            subtitle("00:00:05,000", "00:00:06,000", "Third"),
+        ];
+        srt.write_options.index_start = 0;
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().next(), Some("0"));
+        let indices: Vec<&str> = output
+            .split("\n\n")
+            .map(|b| b.lines().next().unwrap())
+            .collect();
+        assert_eq!(indices, vec!["0", "1", "2"]);
+
+        srt.write_options.index_start = 10;
+        srt.write_options.index_step = 10;
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let indices: Vec<&str> = output
+            .split("\n\n")
+            .map(|b| b.lines().next().unwrap())
+            .collect();
+        assert_eq!(indices, vec!["10", "20", "30"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_srt_json_round_trip() {
+        let srt = SRT {
+            file_path: "test.srt".to_string(),
+            subtitles: vec![
+                Subtitle {
+                    index: 1,
+                    start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+                    end_time: Timestamp::from_string("00:00:04,000").unwrap(),
+                    text: "Hello, world!".to_string(),
+                    settings: None,
+                },
+                Subtitle {
+                    index: 2,
+                    start_time: Timestamp::from_string("00:00:05,000").unwrap(),
+                    end_time: Timestamp::from_string("00:00:08,500").unwrap(),
+                    text: "مرحبا".to_string(),
+                    settings: None,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let json = srt.to_json().unwrap();
+        assert!(json.contains("\"00:00:08,500\""));
+
+        let parsed: SRT = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.file_path, srt.file_path);
+        assert_eq!(parsed.subtitles, srt.subtitles);
+    }
+
+    #[test]
+    fn test_srt_write_file_round_trip() {
+        let input_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_files/round_trip/canonical.srt"
+        );
+        let output_path = std::env::temp_dir().join(format!(
+            "shu-2al_test_srt_write_file_round_trip_{}.srt",
+            std::process::id()
+        ));
+
+        let mut srt = SRT::new(input_path);
+        srt.read_file().unwrap();
+        srt.write_file(output_path.to_str().unwrap()).unwrap();
+        let written = std::fs::read(&output_path).unwrap();
+        std::fs::remove_file(&output_path).unwrap();
+
+        assert_eq!(written, std::fs::read(input_path).unwrap());
+    }
+
+    #[test]
+    fn test_srt_write_to_keep_blank_lines() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![subtitle("00:00:01,000", "00:00:02,000", "First\n\nSecond")];
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1\n00:00:01,000 --> 00:00:02,000\nFirst\nSecond\n"
+        );
+
+        srt.write_options.keep_blank_lines = true;
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1\n00:00:01,000 --> 00:00:02,000\nFirst\n\nSecond\n"
+        );
+    }
+
+    #[test]
+    fn test_srt_write_to_settings() {
+        let content = "1\n00:00:01,000 --> 00:00:02,000 X1:100 X2:200 Y1:10 Y2:30\nPositioned\n\n\
+                       2\n00:00:03,000 --> 00:00:04,000\nPlain\n";
+        let mut srt = SRT::new("test.srt");
+        srt.parse(content).unwrap();
+        assert_eq!(
+            srt.subtitles[0].settings.as_deref(),
+            Some("X1:100 X2:200 Y1:10 Y2:30")
+        );
+
+        let mut output = Vec::new();
+        srt.write_to(&mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), content);
+    }
+
+    #[test]
+    fn test_srt_write_to_idempotent() {
+        let mut srt = SRT::new("test.srt");
+        srt.subtitles = vec![
+            subtitle("00:00:01,000", "00:00:02,000", "Line one\n\n  \nLine two"),
+            subtitle("00:00:03,000", "00:00:04,000", "Second"),
+        ];
+
+        let mut first = Vec::new();
+        srt.write_to(&mut first).unwrap();
+        let mut read = SRT::new("test.srt");
+        read.parse(&String::from_utf8(first.clone()).unwrap())
+            .unwrap();
+        let mut second = Vec::new();
+        read.write_to(&mut second).unwrap();
+
+        assert_eq!(read.subtitles.len(), 2);
+        assert_eq!(read.subtitles[0].text, "Line one\nLine two");
+        assert_eq!(second, first);
+    }
+
+    #[test]
+    fn test_srt_write_file() {
+        let input_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/test_3/input.srt");
+        let expected_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_files/write/expected.srt");
+        let output_path = std::env::temp_dir().join("shu-2al_test_srt_write_file.srt");
+
+        let mut srt = SRT::new(input_path);
+        srt.read_file().unwrap();
+        let written = srt.write_file(output_path.to_str().unwrap()).unwrap();
+        assert_eq!(written, srt.subtitles.len());
+
+        let output = std::fs::read(&output_path).unwrap();
+        std::fs::remove_file(&output_path).unwrap();
+        assert_eq!(output, std::fs::read(expected_path).unwrap());
+    }
 }
@@ -1,8 +1,16 @@
-use std::time::Duration;
+use std::{str::FromStr, time::Duration};
 
 use crate::core::error::SRTError;
 
-use super::{direction::Direction, timestamp::Timestamp};
+use unicode_normalization::UnicodeNormalization;
+use unicode_properties::{GeneralCategory, GeneralCategoryGroup, UnicodeGeneralCategory};
+
+use super::{
+    direction::Direction,
+    options::{ParseOptions, ValidationOptions},
+    text_direction::TextDirection,
+    timestamp::Timestamp,
+};
 
 /// This module provides functionality to clean and format SRT (SubRip Subtitle) files.
 /// It includes functions to read SRT files, remove empty lines, and format the subtitles.
@@ -10,6 +18,7 @@ use super::{direction::Direction, timestamp::Timestamp};
 /// Subtitle struct
 /// Represents a subtitle entry with start time, end time, and text.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Subtitle {
     /// Index of the subtitle (not used in processing, but can be useful for reference)
     pub index: usize,
@@ -19,6 +28,13 @@ pub struct Subtitle {
     pub end_time: Timestamp,
     /// Text of the subtitle
     pub text: String,
+    /// Settings following the end time on the timestamp line, such as the SRT coordinates
+    /// extension (`X1:100 X2:200 Y1:10 Y2:30`) or WebVTT cue settings
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub settings: Option<String>,
 }
 
 #[cfg(test)]
@@ -29,6 +45,7 @@ impl Default for Subtitle {
             start_time: Timestamp::from_string("00:00:01,000").unwrap(),
             end_time: Timestamp::from_string("00:00:05,000").unwrap(),
             text: "Hello, World!".to_string(),
+            settings: None,
         }
     }
 }
@@ -43,10 +60,181 @@ const WORDS_LIST: [&str; 7] = [
     "jungle",
 ];
 
+/// Brackets that enclose sound descriptions by default, e.g. `[door creaks]` or `(laughs)`.
+pub const SOUND_CUE_BRACKETS: [(char, char); 2] = [('[', ']'), ('(', ')')];
+
+/// Removes every segment enclosed by `open` and `close` (inclusive) from the text.
+/// An unterminated segment is left untouched.
+fn remove_enclosed(text: &str, open: char, close: char) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find(open) {
+        let (before, segment) = rest.split_at(i);
+        match segment[open.len_utf8()..].find(close) {
+            Some(end) => {
+                result.push_str(before);
+                rest = &segment[open.len_utf8() + end + close.len_utf8()..];
+            }
+            None => break,
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Removes the segments enclosed by `open` and `close` (inclusive) at the start and end of a
+/// line of text, such as `[door creaks] Who's there?`. Segments inside the line, as in
+/// `I (think) so`, are kept, and so is a leading dialogue dash.
+fn remove_edge_enclosed(line: &str, open: char, close: char) -> String {
+    let line = line.trim();
+    let body = line.strip_prefix('-').unwrap_or(line).trim_start();
+    let dash = &line[..line.len() - body.len()];
+
+    let mut rest = body;
+    loop {
+        if let Some(inner) = rest.strip_prefix(open) {
+            if let Some(end) = inner.find(close) {
+                rest = inner[end + close.len_utf8()..].trim_start();
+                continue;
+            }
+        }
+        if let Some(inner) = rest.strip_suffix(close) {
+            if let Some(start) = inner.rfind(open) {
+                rest = inner[..start].trim_end();
+                continue;
+            }
+        }
+        break;
+    }
+
+    if rest.is_empty() {
+        String::new()
+    } else {
+        format!("{}{}", dash, rest)
+    }
+}
+
+/// Removes a leading speaker label from a line of text, returning the rest of the line.
+///
+/// A label is an optional `-` dash followed by an uppercase name and a colon, such as
+/// `JOHN:` or `- MARY SMITH:`. If `bracketed` is set, names in brackets such as `[John]` are
+/// also recognized, with or without a trailing colon.
+fn remove_speaker_label(line: &str, bracketed: bool) -> &str {
+    let rest = line.trim_start();
+    let rest = rest.strip_prefix('-').unwrap_or(rest).trim_start();
+
+    if let Some((name, dialogue)) = rest.split_once(':') {
+        let is_label = name.chars().any(char::is_uppercase)
+            && name
+                .chars()
+                .all(|c| c.is_uppercase() || c.is_numeric() || " .'-".contains(c));
+        if is_label {
+            return dialogue.trim_start();
+        }
+    }
+
+    if bracketed {
+        if let Some((name, dialogue)) = rest.strip_prefix('[').and_then(|rest| rest.split_once(']'))
+        {
+            if !name.trim().is_empty() {
+                let dialogue = dialogue.strip_prefix(':').unwrap_or(dialogue);
+                return dialogue.trim_start();
+            }
+        }
+    }
+
+    line
+}
+
+/// Splits the text into formatting tags and the text around them.
+///
+/// Tags are HTML-like tags (`<i>`, `</b>`, `<font color="...">`, ...) and ASS override
+/// blocks (`{\\an8}`, `{\\i1}`, ...). Unterminated tags are treated as text.
+///
+/// # Returns
+///
+/// * `Vec<(bool, &str)>` - The segments of the text in order, each paired with whether it is a tag.
+fn split_tags(text: &str) -> Vec<(bool, &str)> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut search_from = 0;
+    while let Some(i) = text[search_from..]
+        .find(['<', '{'])
+        .map(|i| i + search_from)
+    {
+        let candidate = &text[i..];
+        let closing = if candidate.starts_with('<') {
+            candidate.find('>')
+        } else if candidate.starts_with("{\\") {
+            candidate.find('}')
+        } else {
+            None
+        };
+
+        match closing {
+            Some(end) => {
+                if text_start < i {
+                    segments.push((false, &text[text_start..i]));
+                }
+                segments.push((true, &candidate[..=end]));
+                text_start = i + end + 1;
+                search_from = text_start;
+            }
+            // Not a tag, carry on after the opening character
+            None => search_from = i + 1,
+        }
+    }
+    if text_start < text.len() {
+        segments.push((false, &text[text_start..]));
+    }
+    segments
+}
+
+/// Folds text for diacritic-insensitive matching: compatibility variants are decomposed
+/// (NFKD), nonspacing marks such as the Arabic harakat are removed along with the tatweel,
+/// and the result is recomposed (NFC).
+fn fold_diacritics(text: &str) -> String {
+    text.nfkd()
+        .filter(|&c| c.general_category() != GeneralCategory::NonspacingMark && c != '\u{0640}')
+        .nfc()
+        .collect()
+}
+
+/// Checks if a character is whitespace, ASCII punctuation (which includes symbols such as
+/// `~`, `+` and `|`) or belongs to any Unicode punctuation category.
+fn is_punctuation_or_whitespace(c: char) -> bool {
+    c.is_whitespace()
+        || c.is_ascii_punctuation()
+        || c.general_category_group() == GeneralCategoryGroup::Punctuation
+}
+
+/// Removes formatting tags from the given text, see [`split_tags`].
+fn remove_tags(text: &str) -> String {
+    split_tags(text)
+        .into_iter()
+        .filter(|(is_tag, _)| !is_tag)
+        .map(|(_, segment)| segment)
+        .collect()
+}
+
+/// Applies `f` to the text around the formatting tags, leaving the tags untouched.
+fn map_outside_tags(text: &str, f: impl Fn(&str) -> String) -> String {
+    split_tags(text)
+        .into_iter()
+        .map(|(is_tag, segment)| {
+            if is_tag {
+                segment.to_string()
+            } else {
+                f(segment)
+            }
+        })
+        .collect()
+}
+
 impl Subtitle {
     /// Creates a new `Subtitle` instance from a slice of strings.
     /// The first line is the index, the second line contains the start and end time,
-    /// and the following line contain the text.
+    /// and the following lines contain the text.
     ///
     /// # Arguments
     ///
@@ -55,42 +243,108 @@ impl Subtitle {
     /// # Returns
     ///
     ///  * `Result<Subtitle, String>` - Returns a `Subtitle` instance if successful, or an error message if it fails.
+    ///
+    /// # Notes
+    ///
+    /// A subtitle whose end time is before its start time is rejected. A zero-duration
+    /// subtitle (equal start and end time) is allowed.
     pub fn new(lines: &Vec<&str>) -> Result<Self, String> {
+        let subtitle = Subtitle::parse(lines)?;
+        if subtitle.text.is_empty() {
+            return Err("No text provided".to_owned());
+        }
+
+        if !subtitle.is_valid() {
+            return Err("Invalid subtitle".to_owned());
+        }
+
+        Ok(subtitle)
+    }
+
+    /// Parses a subtitle block without checking whether its text is valid.
+    /// Unlike [`Subtitle::new`], the text may be empty.
+    ///
+    /// The index is read from the line preceding the timestamp line if there is one, and
+    /// defaults to `0` otherwise. Text following the end time on the timestamp line is kept in
+    /// `settings`. A block must contain exactly one timestamp line, preceded by
+    /// at most one line, which must then be a numeric index.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - A slice of strings representing the lines of a subtitle block.
+    ///
+    /// # Returns
+    ///
+    ///  * `Result<Subtitle, String>` - Returns a `Subtitle` instance if the block is well-formed, or an error message if it is not.
+    pub fn parse(lines: &[&str]) -> Result<Self, String> {
+        Subtitle::parse_with(lines, &ParseOptions::default())
+    }
+
+    /// Parses a subtitle block like [`Subtitle::parse`], following the timestamp format, text
+    /// trimming and minimum duration settings of the given options.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - A slice of strings representing the lines of a subtitle block.
+    /// * `options` - The parser options, only `lenient_timestamps`, `trim_text`,
+    ///   `min_duration` and `extend_short_cues` are used.
+    ///
+    /// # Returns
+    ///
+    ///  * `Result<Subtitle, String>` - Returns a `Subtitle` instance if the block is well-formed, or an error message if it is not.
+    pub fn parse_with(lines: &[&str], options: &ParseOptions) -> Result<Self, String> {
         // find index of the line with the start and end time
         let ts_i = lines
             .iter()
             .position(|&line| line.contains("-->"))
             .ok_or("No timestamp found")?;
-        if ts_i + 1 >= lines.len() {
-            return Err("No text provided".to_owned());
+        if lines[ts_i + 1..].iter().any(|line| line.contains("-->")) {
+            return Err("Multiple timestamp lines found".to_owned());
         }
-
-        let start_time = lines[ts_i]
-            .split(" --> ")
-            .next()
-            .ok_or("Invalid start timestamp")?
-            .to_string();
-        let end_time = lines[ts_i]
-            .split(" --> ")
-            .nth(1)
-            .ok_or("Invalid end timestamp")?
-            .to_string();
-
-        let text = lines[ts_i + 1].trim().to_string();
-
-        if start_time == "00:03:11,080" {
-            tracing::info!("text: {:?}, empty {}", text, text.is_empty());
+        if ts_i > 1 {
+            return Err("Unexpected lines before timestamp".to_owned());
         }
 
-        let subtitle = Subtitle {
-            index: 0,
-            start_time: Timestamp::from_string(&start_time)?,
-            end_time: Timestamp::from_string(&end_time)?,
+        // Tolerate irregular spacing around the arrow, or none at all
+        let (start_time, end_time) = lines[ts_i].split_once("-->").ok_or("No timestamp found")?;
+        // Anything after the end time is kept as the settings of the subtitle
+        let (end_time, settings) = match end_time.trim().split_once(char::is_whitespace) {
+            Some((end_time, settings)) => (end_time, Some(settings.trim().to_string())),
+            None => (end_time.trim(), None),
+        };
+        let start_time = start_time.trim();
+
+        let text = lines[ts_i + 1..]
+            .iter()
+            .map(|&line| if options.trim_text { line.trim() } else { line })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let index = match ts_i {
+            0 => 0,
+            _ => lines[0].trim().parse().map_err(|_| "Invalid index")?,
+        };
+
+        let mut subtitle = Subtitle {
+            index,
+            start_time: Timestamp::from_string_with(start_time, options.lenient_timestamps)?,
+            end_time: Timestamp::from_string_with(end_time, options.lenient_timestamps)?,
             text,
+            settings,
         };
 
-        if !subtitle.is_valid() {
-            return Err("Invalid subtitle".to_owned());
+        if subtitle.end_time < subtitle.start_time {
+            return Err("End time before start time".to_owned());
+        }
+
+        if let Some(min) = options
+            .min_duration
+            .filter(|&min| subtitle.duration() < min)
+        {
+            if !options.extend_short_cues {
+                return Err("Duration shorter than minimum".to_owned());
+            }
+            subtitle.end_time = subtitle.start_time.saturating_add(min);
         }
 
         Ok(subtitle)
@@ -98,7 +352,7 @@ impl Subtitle {
 
     /// Converts the `Subtitle` instance to a string representation.
     /// The format is:
-    /// ```
+    /// ```text
     /// 00:00:01,000 --> 00:00:05,000
     /// Hello, World!
     ///
@@ -112,7 +366,7 @@ impl Subtitle {
     ///
     /// You should add the number of the subtitle before the start time.
     /// For example:
-    /// ```
+    /// ```text
     /// 1
     /// 00:00:01,000 --> 00:00:05,000
     /// Hello, World!
@@ -122,17 +376,206 @@ impl Subtitle {
         format!("{} --> {}\n{}\n", self.start_time, self.end_time, self.text)
     }
 
+    /// Replaces the text of the subtitle if the new text keeps it valid, see
+    /// [`Subtitle::is_valid`].
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The new text.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Returns `Ok(())` if the text was set, or the reason it was rejected. The subtitle is unchanged on error.
+    pub fn set_text(&mut self, text: String) -> Result<(), String> {
+        let previous = std::mem::replace(&mut self.text, text);
+        if let Some(reason) = self.invalid_reason_with(&ValidationOptions::default()) {
+            self.text = previous;
+            return Err(reason);
+        }
+        Ok(())
+    }
+
+    /// Sets the start time of the subtitle if it is not after the end time.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_time` - The new start time.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Returns `Ok(())` if the start time was set, or an error message if it is after the end time.
+    pub fn set_start(&mut self, start_time: Timestamp) -> Result<(), String> {
+        if self.end_time < start_time {
+            return Err("End time before start time".to_owned());
+        }
+        self.start_time = start_time;
+        Ok(())
+    }
+
+    /// Sets the end time of the subtitle if it is not before the start time.
+    ///
+    /// # Arguments
+    ///
+    /// * `end_time` - The new end time.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Returns `Ok(())` if the end time was set, or an error message if it is before the start time.
+    pub fn set_end(&mut self, end_time: Timestamp) -> Result<(), String> {
+        if end_time < self.start_time {
+            return Err("End time before start time".to_owned());
+        }
+        self.end_time = end_time;
+        Ok(())
+    }
+
+    /// Counts the lines of the text.
+    pub fn line_count(&self) -> usize {
+        self.text.lines().count()
+    }
+
+    /// Counts the characters (Unicode scalar values) of each line of the text. Formatting
+    /// tags are not displayed, so they are not counted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The number of characters of each line.
+    pub fn line_lengths(&self) -> Vec<usize> {
+        self.text
+            .lines()
+            .map(|line| remove_tags(line).chars().count())
+            .collect()
+    }
+
+    /// Detects the dominant writing direction of the text from its first strong character.
+    /// Formatting tags are ignored.
+    ///
+    /// # Returns
+    ///
+    /// * `TextDirection` - The direction of the text, see [`TextDirection::detect`].
+    pub fn text_direction(&self) -> TextDirection {
+        TextDirection::detect(&remove_tags(&self.text))
+    }
+
+    /// Compares the timing and text of two subtitles, ignoring their indices and cue settings,
+    /// consistently with [`SRT::diff`](crate::core::srt::SRT::diff).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The subtitle to compare with.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Returns `true` if both subtitles have the same start time, end time, and text.
+    pub fn content_eq(&self, other: &Subtitle) -> bool {
+        self.start_time == other.start_time
+            && self.end_time == other.end_time
+            && self.text == other.text
+    }
+
+    /// Returns the subtitle as display-ready strings, e.g. for a row in a table.
+    ///
+    /// # Returns
+    ///
+    /// * `(String, String, String)` - The formatted start time, end time, and text.
+    pub fn display_row(&self) -> (String, String, String) {
+        (
+            self.start_time.to_string(),
+            self.end_time.to_string(),
+            self.text.clone(),
+        )
+    }
+
     /// Checks if the subtitle is valid.
     /// A subtitle is considered valid if it has a non-empty start time, end time, and text.
     ///
+    /// Formatting tags are ignored when checking the text, so a line made up of tags and
+    /// punctuation only is judged on its punctuation.
+    ///
     /// # Returns
     ///
     /// * `bool` - Returns `true` if the subtitle is valid, `false` otherwise.
     pub fn is_valid(&self) -> bool {
-        !self.text.is_empty()
-            && !WORDS_LIST.iter().any(|&word| self.text.contains(word))
-            // and text isn't made up of special characters
-            && !self.text.chars().all(|c| c.is_ascii_punctuation())
+        self.is_valid_with(&ValidationOptions::default())
+    }
+
+    /// Checks if the subtitle is valid according to the given validation options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The validation rules to apply.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Returns `true` if the subtitle is valid, `false` otherwise.
+    pub fn is_valid_with(&self, options: &ValidationOptions) -> bool {
+        self.invalid_reason_with(options).is_none()
+    }
+
+    /// Explains why the subtitle is invalid according to the given validation options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The validation rules to apply.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The reason the subtitle is invalid, or `None` if it is valid.
+    pub fn invalid_reason_with(&self, options: &ValidationOptions) -> Option<String> {
+        let text = remove_tags(&self.text);
+        if text.is_empty() {
+            return Some("Empty text".to_string());
+        }
+        if let Some(phrase) = self.banned_match_with(&WORDS_LIST, options) {
+            return Some(format!("Contains banned phrase \"{}\"", phrase));
+        }
+        // and text isn't made up of special characters
+        if options.reject_punctuation_only && text.chars().all(is_punctuation_or_whitespace) {
+            return Some("Punctuation only".to_string());
+        }
+        None
+    }
+
+    /// Finds the first phrase of a list contained in the text, e.g. to show why a subtitle was
+    /// dropped. Validation uses it with the built-in list of banned phrases.
+    ///
+    /// # Arguments
+    ///
+    /// * `list` - The phrases to look for, in order of priority.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The first phrase of `list` found in the text, or `None` if there is
+    ///   none.
+    pub fn banned_match<S: AsRef<str>>(&self, list: &[S]) -> Option<String> {
+        self.banned_match_with(list, &ValidationOptions::default())
+    }
+
+    /// Finds the first phrase of a list contained in the text like [`Subtitle::banned_match`],
+    /// ignoring diacritics if `options.ignore_diacritics` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `list` - The phrases to look for, in order of priority.
+    /// * `options` - The validation rules, only `ignore_diacritics` is used.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The first phrase of `list` found in the text, as written in `list`,
+    ///   or `None` if there is none.
+    pub fn banned_match_with<S: AsRef<str>>(
+        &self,
+        list: &[S],
+        options: &ValidationOptions,
+    ) -> Option<String> {
+        let mut phrases = list.iter().map(AsRef::as_ref);
+        let found = if options.ignore_diacritics {
+            let text = fold_diacritics(&self.text);
+            phrases.find(|phrase| text.contains(&fold_diacritics(phrase)))
+        } else {
+            phrases.find(|phrase| self.text.contains(phrase))
+        };
+        found.map(str::to_string)
     }
 
     pub fn duration(&self) -> Duration {
@@ -141,6 +584,167 @@ impl Subtitle {
         Duration::from_millis(end_time - start_time)
     }
 
+    /// Computes the reading speed of the subtitle in characters per second.
+    /// Whitespace is not counted towards the number of characters.
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The characters per second. A zero-duration subtitle returns `f64::INFINITY`
+    ///   if it has any text, or `0.0` otherwise.
+    pub fn cps(&self) -> f64 {
+        let chars = self.text.chars().filter(|c| !c.is_whitespace()).count();
+        let duration = self.duration();
+        if chars == 0 {
+            0.0
+        } else if duration.is_zero() {
+            f64::INFINITY
+        } else {
+            chars as f64 / duration.as_secs_f64()
+        }
+    }
+
+    /// Removes formatting tags such as `<i>`, `<font color="...">` and `{\an8}` from the text,
+    /// preserving the surrounding words and spacing.
+    pub fn strip_tags(&mut self) {
+        self.text = remove_tags(&self.text);
+    }
+
+    /// Removes sound-effect markers from the text, such as `♪ lyrics ♪`, `[music]` or
+    /// `(door creaks)`, along with the whitespace and lines they leave behind.
+    ///
+    /// Bracketed segments are only removed at the start or end of a line, so parenthetical
+    /// dialogue such as `I (think) so` is kept.
+    ///
+    /// The text may become empty, in which case the subtitle is no longer valid.
+    pub fn remove_sound_cues(&mut self) {
+        self.remove_sound_cues_with(&SOUND_CUE_BRACKETS);
+    }
+
+    /// Same as [`Subtitle::remove_sound_cues`], but with a custom set of brackets.
+    ///
+    /// # Arguments
+    ///
+    /// * `brackets` - Pairs of opening and closing characters enclosing sound descriptions.
+    pub fn remove_sound_cues_with(&mut self, brackets: &[(char, char)]) {
+        let text = remove_enclosed(&self.text, '♪', '♪').replace('♪', "");
+
+        self.text = text
+            .lines()
+            .map(|line| {
+                brackets
+                    .iter()
+                    .fold(line.to_string(), |line, &(open, close)| {
+                        remove_edge_enclosed(&line, open, close)
+                    })
+            })
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    /// Re-wraps the text onto multiple lines at word boundaries, so that no line exceeds
+    /// `max_chars` characters. Words longer than `max_chars` are not split and are left on
+    /// their own line.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_chars` - The maximum number of characters per line.
+    pub fn wrap(&mut self, max_chars: usize) {
+        let mut lines: Vec<String> = Vec::new();
+        let mut current = String::new();
+        for word in self.text.split_whitespace() {
+            if current.is_empty() {
+                current.push_str(word);
+            } else if current.chars().count() + 1 + word.chars().count() <= max_chars {
+                current.push(' ');
+                current.push_str(word);
+            } else {
+                lines.push(std::mem::take(&mut current));
+                current.push_str(word);
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+
+        self.text = lines.join("\n");
+    }
+
+    /// Trims every line of the text, collapses runs of blank lines into a single blank line,
+    /// and drops leading and trailing blank lines.
+    pub fn normalize_text(&mut self) {
+        let mut lines: Vec<&str> = Vec::new();
+        for line in self.text.lines().map(str::trim) {
+            if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+                continue;
+            }
+            lines.push(line);
+        }
+        lines.pop_if(|line| line.is_empty());
+        self.text = lines.join("\n");
+    }
+
+    /// Checks whether the text has a blank line between two lines of content, which some
+    /// players render as a gap.
+    pub fn has_internal_blank_lines(&self) -> bool {
+        self.text.trim().lines().any(|line| line.trim().is_empty())
+    }
+
+    /// Removes the blank lines of the text, keeping its other lines as they are.
+    pub fn compact_lines(&mut self) {
+        self.text = self
+            .text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    /// Joins the lines of the text into a single line, collapsing newlines and runs of
+    /// whitespace into single spaces.
+    pub fn unwrap(&mut self) {
+        self.text = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+
+    /// Converts the text to uppercase. Formatting tags are left untouched.
+    ///
+    /// The conversion is Unicode-aware, e.g. `é` becomes `É` and `ß` becomes `SS`, while
+    /// scripts without case, such as Arabic, are unchanged.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_uppercase(&mut self) {
+        self.text = map_outside_tags(&self.text, str::to_uppercase);
+    }
+
+    /// Converts the text to lowercase. Formatting tags are left untouched.
+    ///
+    /// The conversion is Unicode-aware, see [`Subtitle::to_uppercase`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_lowercase(&mut self) {
+        self.text = map_outside_tags(&self.text, str::to_lowercase);
+    }
+
+    /// Removes leading speaker labels, such as `JOHN:` or `- MARY:`, from every line of the
+    /// text, leaving the dialogue. Lines left empty are removed.
+    pub fn strip_speaker_labels(&mut self) {
+        self.strip_speaker_labels_with(false);
+    }
+
+    /// Same as [`Subtitle::strip_speaker_labels`], optionally recognizing bracketed labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `bracketed` - Whether to also remove labels in brackets, such as `[John]` or `[JOHN]:`.
+    pub fn strip_speaker_labels_with(&mut self, bracketed: bool) {
+        self.text = self
+            .text
+            .lines()
+            .map(|line| remove_speaker_label(line, bracketed))
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
     pub fn move_start(&mut self, delta: &Duration, direction: &Direction) -> Result<(), SRTError> {
         self.start_time.move_ts(delta, direction)
     }
@@ -152,6 +756,42 @@ impl Subtitle {
         self.move_end(delta, direction)?;
         Ok(())
     }
+
+    /// Moves the subtitle by a signed number of milliseconds, keeping its duration.
+    ///
+    /// A subtitle moved before `00:00:00,000` is clamped to start at zero, its end following
+    /// so that the duration is still preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The offset in milliseconds, negative values move the subtitle backward.
+    pub fn offset_millis(&mut self, delta: i64) {
+        let duration = self.duration();
+        let start = self.start_time.to_millis().saturating_add_signed(delta);
+        self.start_time = Timestamp::from_millis(start);
+        self.end_time = self.start_time.saturating_add(duration);
+    }
+}
+
+/// Parses a full subtitle block, see [`Subtitle::parse`].
+///
+/// # Examples
+///
+/// ```rust
+/// use shu_2al::core::subtitle::Subtitle;
+///
+/// let subtitle: Subtitle = "1\n00:00:01,000 --> 00:00:05,000\nHello, World!".parse().unwrap();
+///
+/// assert_eq!(subtitle.index, 1);
+/// assert_eq!(subtitle.text, "Hello, World!");
+/// ```
+impl FromStr for Subtitle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().collect();
+        Subtitle::parse(&lines)
+    }
 }
 
 #[cfg(test)]
@@ -208,35 +848,320 @@ mod tests {
     }
 
     #[test]
-    fn test_subtitle_to_string() {
-        let subtitle = Subtitle {
-            index: 0,
-            start_time: Timestamp::from_string("00:00:01,000").unwrap(),
-            end_time: Timestamp::from_string("00:00:05,000").unwrap(),
-            text: "Hello, World!".to_string(),
-        };
+    fn test_subtitle_new_inverted_timestamps() {
+        let lines = vec!["00:00:05,000 --> 00:00:01,000", "Hello, World!"];
         assert_eq!(
-            subtitle.to_string(),
-            "00:00:01,000 --> 00:00:05,000\nHello, World!\n"
+            Subtitle::new(&lines),
+            Err("End time before start time".to_string())
         );
     }
 
     #[test]
-    fn test_subtitle_is_valid() {
-        let valid_subtitle = Subtitle {
-            text: "Hello, World!".to_string(),
-            ..Default::default()
-        };
-        assert!(valid_subtitle.is_valid());
+    fn test_subtitle_new_multiple_lines() {
+        let lines = vec!["1", "00:00:01,000 --> 00:00:05,000", "Hello,", "World!"];
+        assert_eq!(Subtitle::new(&lines).unwrap().text, "Hello,\nWorld!");
     }
 
     #[test]
-    fn test_subtitle_bad_text() {
-        let invalid_subtitle = Subtitle {
-            text: "".to_string(),
-            ..Default::default()
-        };
-        assert!(!invalid_subtitle.is_valid());
+    fn test_subtitle_parse_empty_text() {
+        let lines = vec!["1", "00:00:01,000 --> 00:00:05,000"];
+        assert_eq!(Subtitle::parse(&lines).unwrap().text, "");
+        assert!(Subtitle::parse(&["1", "Hello, World!"]).is_err());
+    }
+
+    #[test]
+    fn test_subtitle_new_arrow_spacing() {
+        for timestamp_line in [
+            "00:00:01,000  -->  00:00:05,000",
+            "00:00:01,000\t-->   00:00:05,000 ",
+            "00:00:01,000-->00:00:05,000",
+        ] {
+            let subtitle = Subtitle::new(&vec!["1", timestamp_line, "Hello"]).unwrap();
+            assert_eq!(subtitle.start_time.to_string(), "00:00:01,000");
+            assert_eq!(subtitle.end_time.to_string(), "00:00:05,000");
+        }
+    }
+
+    #[test]
+    fn test_subtitle_parse_settings() {
+        let lines = vec![
+            "1",
+            "00:00:01,000 --> 00:00:05,000  X1:100 X2:200 Y1:10 Y2:30",
+            "Hi",
+        ];
+        let subtitle = Subtitle::parse(&lines).unwrap();
+        assert_eq!(subtitle.end_time.to_string(), "00:00:05,000");
+        assert_eq!(
+            subtitle.settings.as_deref(),
+            Some("X1:100 X2:200 Y1:10 Y2:30")
+        );
+
+        let lines = vec!["1", "00:00:01,000 --> 00:00:05,000 ", "Hi"];
+        assert_eq!(Subtitle::parse(&lines).unwrap().settings, None);
+    }
+
+    #[test]
+    fn test_subtitle_parse_min_duration() {
+        let lines = vec!["1", "00:00:01,000 --> 00:00:01,000", "Blink"];
+
+        let subtitle = Subtitle::parse(&lines).unwrap();
+        assert_eq!(subtitle.duration(), Duration::ZERO);
+
+        let mut options = ParseOptions {
+            min_duration: Some(Duration::from_millis(500)),
+            ..Default::default()
+        };
+        assert_eq!(
+            Subtitle::parse_with(&lines, &options).unwrap_err(),
+            "Duration shorter than minimum"
+        );
+
+        options.extend_short_cues = true;
+        let subtitle = Subtitle::parse_with(&lines, &options).unwrap();
+        assert_eq!(subtitle.end_time.to_string(), "00:00:01,500");
+    }
+
+    #[test]
+    fn test_subtitle_parse_index() {
+        let lines = vec!["42", "00:00:01,000 --> 00:00:05,000", "Hello, World!"];
+        assert_eq!(Subtitle::parse(&lines).unwrap().index, 42);
+
+        let lines = vec!["00:00:01,000 --> 00:00:05,000", "Hello, World!"];
+        assert_eq!(Subtitle::parse(&lines).unwrap().index, 0);
+
+        let lines = vec!["one", "00:00:01,000 --> 00:00:05,000", "Hello, World!"];
+        assert_eq!(Subtitle::parse(&lines).unwrap_err(), "Invalid index");
+    }
+
+    #[test]
+    fn test_subtitle_parse_multiple_timestamps() {
+        let lines = vec![
+            "1",
+            "00:00:01,000 --> 00:00:05,000",
+            "00:00:06,000 --> 00:00:07,000",
+            "Hello, World!",
+        ];
+        assert_eq!(
+            Subtitle::parse(&lines).unwrap_err(),
+            "Multiple timestamp lines found"
+        );
+    }
+
+    #[test]
+    fn test_subtitle_parse_stray_numeric_line() {
+        let lines = vec!["7", "1", "00:00:01,000 --> 00:00:05,000", "Hello, World!"];
+        assert_eq!(
+            Subtitle::parse(&lines).unwrap_err(),
+            "Unexpected lines before timestamp"
+        );
+    }
+
+    #[test]
+    fn test_subtitle_new_zero_duration() {
+        let lines = vec!["00:00:05,000 --> 00:00:05,000", "Hello, World!"];
+        let subtitle = Subtitle::new(&lines).unwrap();
+        assert_eq!(subtitle.duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_subtitle_to_string() {
+        let subtitle = Subtitle {
+            index: 0,
+            start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+            end_time: Timestamp::from_string("00:00:05,000").unwrap(),
+            text: "Hello, World!".to_string(),
+            settings: None,
+        };
+        assert_eq!(
+            subtitle.to_string(),
+            "00:00:01,000 --> 00:00:05,000\nHello, World!\n"
+        );
+    }
+
+    #[test]
+    fn test_subtitle_set_text() {
+        let mut subtitle = Subtitle {
+            text: "Hello, World!".to_string(),
+            ..Default::default()
+        };
+
+        subtitle.set_text("Goodbye!".to_string()).unwrap();
+        assert_eq!(subtitle.text, "Goodbye!");
+
+        assert_eq!(
+            subtitle.set_text("...".to_string()),
+            Err("Punctuation only".to_string())
+        );
+        assert!(subtitle.set_text(String::new()).is_err());
+        assert_eq!(subtitle.text, "Goodbye!");
+    }
+
+    #[test]
+    fn test_subtitle_set_start_end() {
+        let mut subtitle = Subtitle {
+            start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+            end_time: Timestamp::from_string("00:00:05,000").unwrap(),
+            ..Default::default()
+        };
+
+        subtitle
+            .set_start(Timestamp::from_string("00:00:02,000").unwrap())
+            .unwrap();
+        subtitle
+            .set_end(Timestamp::from_string("00:00:04,000").unwrap())
+            .unwrap();
+        assert!(subtitle
+            .set_start(Timestamp::from_string("00:00:04,500").unwrap())
+            .is_err());
+        assert!(subtitle
+            .set_end(Timestamp::from_string("00:00:01,500").unwrap())
+            .is_err());
+
+        assert_eq!(subtitle.start_time.to_string(), "00:00:02,000");
+        assert_eq!(subtitle.end_time.to_string(), "00:00:04,000");
+    }
+
+    #[test]
+    fn test_subtitle_offset_millis() {
+        let mut subtitle = Subtitle {
+            start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+            end_time: Timestamp::from_string("00:00:03,500").unwrap(),
+            ..Default::default()
+        };
+
+        subtitle.offset_millis(1250);
+        assert_eq!(subtitle.start_time.to_string(), "00:00:02,250");
+        assert_eq!(subtitle.end_time.to_string(), "00:00:04,750");
+
+        subtitle.offset_millis(-2000);
+        assert_eq!(subtitle.start_time.to_string(), "00:00:00,250");
+        assert_eq!(subtitle.end_time.to_string(), "00:00:02,750");
+    }
+
+    #[test]
+    fn test_subtitle_offset_millis_clamps_at_zero() {
+        let mut subtitle = Subtitle {
+            start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+            end_time: Timestamp::from_string("00:00:03,500").unwrap(),
+            ..Default::default()
+        };
+
+        subtitle.offset_millis(-5000);
+
+        assert_eq!(subtitle.start_time.to_string(), "00:00:00,000");
+        assert_eq!(subtitle.end_time.to_string(), "00:00:02,500");
+    }
+
+    #[test]
+    fn test_subtitle_line_count() {
+        let subtitle = Subtitle {
+            text: "One\nTwo\nThree".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(subtitle.line_count(), 3);
+    }
+
+    #[test]
+    fn test_subtitle_line_lengths() {
+        let subtitle = Subtitle {
+            text: "<i>Hello</i>\nمرحبا بالعالم".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(subtitle.line_lengths(), vec![5, 13]);
+    }
+
+    #[test]
+    fn test_subtitle_text_direction() {
+        let arabic = Subtitle {
+            text: "<i>مرحبا بالعالم</i>".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(arabic.text_direction(), TextDirection::Rtl);
+
+        let english = Subtitle {
+            text: "Hello, World!".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(english.text_direction(), TextDirection::Ltr);
+    }
+
+    #[test]
+    fn test_subtitle_content_eq() {
+        let subtitle = Subtitle {
+            index: 1,
+            start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+            end_time: Timestamp::from_string("00:00:05,000").unwrap(),
+            text: "Hello, World!".to_string(),
+            settings: None,
+        };
+        let renumbered = Subtitle {
+            index: 7,
+            ..subtitle.clone()
+        };
+        assert_ne!(subtitle, renumbered);
+        assert!(subtitle.content_eq(&renumbered));
+
+        let edited = Subtitle {
+            text: "Hello!".to_string(),
+            ..subtitle.clone()
+        };
+        assert!(!subtitle.content_eq(&edited));
+
+        let positioned = Subtitle {
+            settings: Some("X1:100 X2:200 Y1:10 Y2:30".to_string()),
+            ..subtitle.clone()
+        };
+        assert!(subtitle.content_eq(&positioned));
+    }
+
+    #[test]
+    fn test_subtitle_display_row() {
+        let subtitle = Subtitle {
+            index: 3,
+            start_time: Timestamp::from_string("00:01:02,003").unwrap(),
+            end_time: Timestamp::from_string("00:01:04,500").unwrap(),
+            text: "Hello,\nWorld!".to_string(),
+            settings: None,
+        };
+        assert_eq!(
+            subtitle.display_row(),
+            (
+                "00:01:02,003".to_string(),
+                "00:01:04,500".to_string(),
+                "Hello,\nWorld!".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_subtitle_from_str() {
+        let subtitle: Subtitle = "2\n00:00:01,000 --> 00:00:05,000\nHello,\nWorld!"
+            .parse()
+            .unwrap();
+        assert_eq!(subtitle.index, 2);
+        assert_eq!(subtitle.start_time.to_string(), "00:00:01,000");
+        assert_eq!(subtitle.end_time.to_string(), "00:00:05,000");
+        assert_eq!(subtitle.text, "Hello,\nWorld!");
+
+        assert!("Hello, World!".parse::<Subtitle>().is_err());
+    }
+
+    #[test]
+    fn test_subtitle_is_valid() {
+        let valid_subtitle = Subtitle {
+            text: "Hello, World!".to_string(),
+            ..Default::default()
+        };
+        assert!(valid_subtitle.is_valid());
+    }
+
+    #[test]
+    fn test_subtitle_bad_text() {
+        let invalid_subtitle = Subtitle {
+            text: "".to_string(),
+            ..Default::default()
+        };
+        assert!(!invalid_subtitle.is_valid());
 
         let invalid_subtitle = Subtitle {
             text: "شتركوا في القناة".to_string(),
@@ -257,6 +1182,143 @@ mod tests {
         assert!(!invalid_subtitle.is_valid());
     }
 
+    #[test]
+    fn test_subtitle_is_valid_ignores_tags() {
+        let invalid_subtitle = Subtitle {
+            text: "<i>...</i>".to_string(),
+            ..Default::default()
+        };
+        assert!(!invalid_subtitle.is_valid());
+
+        let invalid_subtitle = Subtitle {
+            text: "<b></b>".to_string(),
+            ..Default::default()
+        };
+        assert!(!invalid_subtitle.is_valid());
+
+        let valid_subtitle = Subtitle {
+            text: "<i>Hello</i>".to_string(),
+            ..Default::default()
+        };
+        assert!(valid_subtitle.is_valid());
+    }
+
+    #[test]
+    fn test_subtitle_is_valid_unicode_punctuation() {
+        let ellipsis = Subtitle {
+            text: "\u{2026}?".to_string(),
+            ..Default::default()
+        };
+        assert!(!ellipsis.is_valid());
+
+        let emoji = Subtitle {
+            text: "\u{1F3B5}\u{1F3B5}".to_string(),
+            ..Default::default()
+        };
+        assert!(emoji.is_valid());
+
+        let keep_punctuation = ValidationOptions {
+            reject_punctuation_only: false,
+            ..Default::default()
+        };
+        assert!(ellipsis.is_valid_with(&keep_punctuation));
+        assert!(emoji.is_valid_with(&keep_punctuation));
+    }
+
+    #[test]
+    fn test_subtitle_is_valid_ascii_symbols() {
+        for text in ["~~~", "+++", "|", "$ < = > ^ `"] {
+            let symbols = Subtitle {
+                text: text.to_string(),
+                ..Default::default()
+            };
+            assert!(!symbols.is_valid(), "{:?} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn test_subtitle_is_valid_arabic_punctuation() {
+        let arabic_punctuation = Subtitle {
+            text: "\u{060C} \u{061B}\n\u{061F}".to_string(),
+            ..Default::default()
+        };
+        assert!(!arabic_punctuation.is_valid());
+        assert_eq!(
+            arabic_punctuation.invalid_reason_with(&ValidationOptions::default()),
+            Some("Punctuation only".to_string())
+        );
+
+        let arabic_question = Subtitle {
+            text: "\u{0644}\u{0645}\u{0627}\u{0630}\u{0627}\u{061F}".to_string(),
+            ..Default::default()
+        };
+        assert!(arabic_question.is_valid());
+
+        let keep_punctuation = ValidationOptions {
+            reject_punctuation_only: false,
+            ..Default::default()
+        };
+        assert!(arabic_punctuation.is_valid_with(&keep_punctuation));
+    }
+
+    #[test]
+    fn test_subtitle_normalize_text() {
+        let mut subtitle = Subtitle {
+            text: "\n  First  \n\n \n\tSecond\n\n".to_string(),
+            ..Default::default()
+        };
+        subtitle.normalize_text();
+        assert_eq!(subtitle.text, "First\n\nSecond");
+    }
+
+    #[test]
+    fn test_subtitle_banned_match() {
+        let subtitle = Subtitle {
+            text: "Don't forget to subscribe to the channel!".to_string(),
+            ..Default::default()
+        };
+        let list = vec!["like and share".to_string(), "subscribe".to_string()];
+
+        assert_eq!(subtitle.banned_match(&list), Some("subscribe".to_string()));
+        assert_eq!(subtitle.banned_match(&list[..1]), None);
+        assert_eq!(subtitle.banned_match(&WORDS_LIST), None);
+    }
+
+    #[test]
+    fn test_subtitle_banned_match_ignore_diacritics() {
+        // "اشتركوا في القناة" with harakat and a tatweel
+        let subtitle = Subtitle {
+            text: "\u{0627}\u{0650}\u{0634}\u{0652}\u{062A}\u{064E}\u{0631}\u{0652}\u{0643}\u{064F}\u{0640}\u{0648}\u{0627} \u{0641}\u{0650}\u{064A} \u{0627}\u{0644}\u{0652}\u{0642}\u{064E}\u{0646}\u{064E}\u{0627}\u{0629}\u{0650}".to_string(),
+            ..Default::default()
+        };
+        assert!(subtitle.is_valid());
+
+        let options = ValidationOptions {
+            ignore_diacritics: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            subtitle.banned_match_with(&WORDS_LIST, &options),
+            Some(WORDS_LIST[0].to_string())
+        );
+        assert!(!subtitle.is_valid_with(&options));
+    }
+
+    #[test]
+    fn test_subtitle_compact_lines() {
+        let mut subtitle = Subtitle {
+            text: "Line one\n\n  \nLine three".to_string(),
+            ..Default::default()
+        };
+        assert!(subtitle.has_internal_blank_lines());
+
+        subtitle.compact_lines();
+
+        assert_eq!(subtitle.text, "Line one\nLine three");
+        assert_eq!(subtitle.text.lines().count(), 2);
+        assert!(!subtitle.has_internal_blank_lines());
+    }
+
     #[test]
     fn test_subtitle_duration() {
         let subtitle = Subtitle {
@@ -264,7 +1326,207 @@ mod tests {
             start_time: Timestamp::from_string("00:00:01,000").unwrap(),
             end_time: Timestamp::from_string("00:00:05,000").unwrap(),
             text: "Hello, World!".to_string(),
+            settings: None,
         };
         assert_eq!(subtitle.duration(), Duration::new(4, 0));
     }
+
+    #[test]
+    fn test_subtitle_strip_italic_tags() {
+        let mut subtitle = Subtitle {
+            text: "<i>Hello</i>, <b>World</b>!".to_string(),
+            ..Default::default()
+        };
+        subtitle.strip_tags();
+        assert_eq!(subtitle.text, "Hello, World!");
+    }
+
+    #[test]
+    fn test_subtitle_strip_font_tags() {
+        let mut subtitle = Subtitle {
+            text: "{\\an8}Say <font color=\"#ff0000\">red</font> again".to_string(),
+            ..Default::default()
+        };
+        subtitle.strip_tags();
+        assert_eq!(subtitle.text, "Say red again");
+    }
+
+    #[test]
+    fn test_subtitle_strip_tags_unterminated() {
+        let mut subtitle = Subtitle {
+            text: "1 < 2 {not a tag}".to_string(),
+            ..Default::default()
+        };
+        subtitle.strip_tags();
+        assert_eq!(subtitle.text, "1 < 2 {not a tag}");
+    }
+
+    #[test]
+    fn test_subtitle_remove_music_notes() {
+        let mut subtitle = Subtitle {
+            text: "♪ La la la ♪".to_string(),
+            ..Default::default()
+        };
+        subtitle.remove_sound_cues();
+        assert_eq!(subtitle.text, "");
+
+        let mut subtitle = Subtitle {
+            text: "♪ Humming ♪ Hello there".to_string(),
+            ..Default::default()
+        };
+        subtitle.remove_sound_cues();
+        assert_eq!(subtitle.text, "Hello there");
+    }
+
+    #[test]
+    fn test_subtitle_remove_bracketed_sound_cues() {
+        let mut subtitle = Subtitle {
+            text: "[door creaks]\nWho's there? (gasps)".to_string(),
+            ..Default::default()
+        };
+        subtitle.remove_sound_cues();
+        assert_eq!(subtitle.text, "Who's there?");
+
+        let mut subtitle = Subtitle {
+            text: "[door creaks] Who's there? (gasps)".to_string(),
+            ..Default::default()
+        };
+        subtitle.remove_sound_cues_with(&[('[', ']')]);
+        assert_eq!(subtitle.text, "Who's there? (gasps)");
+
+        let mut subtitle = Subtitle {
+            text: "- (laughs) Yes.\n- [sighs] [music]".to_string(),
+            ..Default::default()
+        };
+        subtitle.remove_sound_cues();
+        assert_eq!(subtitle.text, "- Yes.");
+    }
+
+    #[test]
+    fn test_subtitle_remove_sound_cues_keeps_inline_brackets() {
+        let mut subtitle = Subtitle {
+            text: "I (think) so.\n(sighs) Fine, [whatever] you say.".to_string(),
+            ..Default::default()
+        };
+        subtitle.remove_sound_cues();
+        assert_eq!(subtitle.text, "I (think) so.\nFine, [whatever] you say.");
+    }
+
+    #[test]
+    fn test_subtitle_wrap() {
+        let mut subtitle = Subtitle {
+            text: "This subtitle line is exactly sixty characters long, see it?".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(subtitle.text.chars().count(), 60);
+
+        subtitle.wrap(42);
+        assert_eq!(
+            subtitle.text,
+            "This subtitle line is exactly sixty\ncharacters long, see it?"
+        );
+    }
+
+    #[test]
+    fn test_subtitle_wrap_long_word() {
+        let mut subtitle = Subtitle {
+            text: "A Supercalifragilisticexpialidocious day".to_string(),
+            ..Default::default()
+        };
+        subtitle.wrap(10);
+        assert_eq!(subtitle.text, "A\nSupercalifragilisticexpialidocious\nday");
+    }
+
+    #[test]
+    fn test_subtitle_unwrap() {
+        let mut subtitle = Subtitle {
+            text: "First line \n  second line\nthird   line".to_string(),
+            ..Default::default()
+        };
+        subtitle.unwrap();
+        assert_eq!(subtitle.text, "First line second line third line");
+    }
+
+    #[test]
+    fn test_subtitle_to_uppercase() {
+        let mut subtitle = Subtitle {
+            text: "<i>Café</i> مرحبا straße".to_string(),
+            ..Default::default()
+        };
+        subtitle.to_uppercase();
+        assert_eq!(subtitle.text, "<i>CAFÉ</i> مرحبا STRASSE");
+        assert_eq!(subtitle.start_time, Subtitle::default().start_time);
+    }
+
+    #[test]
+    fn test_subtitle_to_lowercase() {
+        let mut subtitle = Subtitle {
+            text: "{\\an8}ÉCOLE <FONT COLOR=\"RED\">ΣΟΦΙΑ</FONT> شو قال".to_string(),
+            ..Default::default()
+        };
+        subtitle.to_lowercase();
+        assert_eq!(
+            subtitle.text,
+            "{\\an8}école <FONT COLOR=\"RED\">σοφια</FONT> شو قال"
+        );
+    }
+
+    #[test]
+    fn test_subtitle_strip_speaker_labels() {
+        let mut subtitle = Subtitle {
+            text: "JOHN: Hello".to_string(),
+            ..Default::default()
+        };
+        subtitle.strip_speaker_labels();
+        assert_eq!(subtitle.text, "Hello");
+
+        let mut subtitle = Subtitle {
+            text: "- MARY: Hi\n- JOHN SMITH: Hey there".to_string(),
+            ..Default::default()
+        };
+        subtitle.strip_speaker_labels();
+        assert_eq!(subtitle.text, "Hi\nHey there");
+    }
+
+    #[test]
+    fn test_subtitle_strip_speaker_labels_keeps_dialogue() {
+        let mut subtitle = Subtitle {
+            text: "Note: it's 10:30\n[JOHN] Hello".to_string(),
+            ..Default::default()
+        };
+        subtitle.strip_speaker_labels();
+        assert_eq!(subtitle.text, "Note: it's 10:30\n[JOHN] Hello");
+    }
+
+    #[test]
+    fn test_subtitle_strip_bracketed_speaker_labels() {
+        let mut subtitle = Subtitle {
+            text: "[John] Hello\n- [MARY]: Hi".to_string(),
+            ..Default::default()
+        };
+        subtitle.strip_speaker_labels_with(true);
+        assert_eq!(subtitle.text, "Hello\nHi");
+    }
+
+    #[test]
+    fn test_subtitle_cps() {
+        let subtitle = Subtitle {
+            text: "Hello World".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(subtitle.cps(), 2.5);
+
+        let zero_duration = Subtitle {
+            end_time: Timestamp::from_string("00:00:01,000").unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(zero_duration.cps(), f64::INFINITY);
+
+        let empty = Subtitle {
+            end_time: Timestamp::from_string("00:00:01,000").unwrap(),
+            text: String::new(),
+            ..Default::default()
+        };
+        assert_eq!(empty.cps(), 0.0);
+    }
 }
@@ -0,0 +1,52 @@
+use unicode_bidi::{bidi_class, BidiClass};
+
+/// This module defines the `TextDirection` enum, which represents the writing direction
+/// of subtitle text. Not to be confused with [`super::direction::Direction`], which is the
+/// direction in which timestamps are moved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Left to right, e.g. English.
+    #[default]
+    Ltr,
+    /// Right to left, e.g. Arabic or Hebrew.
+    Rtl,
+}
+
+impl TextDirection {
+    /// Detects the direction of a text from its first strong character, following the
+    /// Unicode bidirectional algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to classify.
+    ///
+    /// # Returns
+    ///
+    /// * `TextDirection` - The direction of the text, `Ltr` if it has no strong character.
+    pub fn detect(text: &str) -> TextDirection {
+        text.chars()
+            .find_map(|c| match bidi_class(c) {
+                BidiClass::L => Some(TextDirection::Ltr),
+                BidiClass::R | BidiClass::AL => Some(TextDirection::Rtl),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_direction_detect() {
+        assert_eq!(TextDirection::detect("Hello"), TextDirection::Ltr);
+        assert_eq!(TextDirection::detect("مرحبا"), TextDirection::Rtl);
+        assert_eq!(TextDirection::detect("שלום"), TextDirection::Rtl);
+        assert_eq!(
+            TextDirection::detect("- 42, مرحبا Hello"),
+            TextDirection::Rtl
+        );
+        assert_eq!(TextDirection::detect("123 ..."), TextDirection::Ltr);
+    }
+}
@@ -1,4 +1,8 @@
-use std::time::Duration;
+use std::{
+    ops::{Add, Sub},
+    str::FromStr,
+    time::Duration,
+};
 
 use crate::core::error::SRTError;
 
@@ -13,32 +17,54 @@ pub struct Timestamp {
 }
 
 impl Timestamp {
-    /// Creates a new `Timestamp` instance from the given hours, minutes, seconds, and milliseconds.
+    /// The latest representable timestamp. Arithmetic saturates at it, see
+    /// [`Timestamp::saturating_add`].
+    pub const MAX: Timestamp = Timestamp {
+        hours: u32::MAX,
+        minutes: 59,
+        seconds: 59,
+        milliseconds: 999,
+    };
+
+    /// Creates a new `Timestamp` instance from a string in the `HH:MM:SS,mmm` format.
     ///
     /// # Arguments
     ///
-    /// * `hours` - The number of hours.
-    /// * `minutes` - The number of minutes.
-    /// * `seconds` - The number of seconds.
-    /// * `milliseconds` - The number of milliseconds.
+    /// * `timestamp_str` - The timestamp string, e.g. `"00:00:01,000"`.
     ///
     /// # Returns
     ///
-    /// * `Timestamp` - Returns a new `Timestamp` instance.
+    /// * `Result<Timestamp, String>` - Returns a new `Timestamp` instance, or an error message if the string is malformed.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use srt::timestamp::Timestamp;
+    /// use shu_2al::core::timestamp::Timestamp;
     ///
-    /// let timestamp = Timestamp::new("00:00:01,000");
+    /// let timestamp = Timestamp::from_string("00:00:01,000").unwrap();
     ///
-    /// assert_eq!(timestamp.hours, 0);
-    /// assert_eq!(timestamp.minutes, 0);
-    /// assert_eq!(timestamp.seconds, 1);
-    /// assert_eq!(timestamp.milliseconds, 0);
+    /// assert_eq!(timestamp.hours(), 0);
+    /// assert_eq!(timestamp.minutes(), 0);
+    /// assert_eq!(timestamp.seconds(), 1);
+    /// assert_eq!(timestamp.milliseconds(), 0);
+    /// assert!(Timestamp::from_string("00:00:01.000").is_err());
     /// ```
     pub fn from_string(timestamp_str: &str) -> Result<Self, String> {
+        Timestamp::from_string_with(timestamp_str, false)
+    }
+
+    /// Creates a new `Timestamp` instance from a string, optionally accepting a `.` as the
+    /// fractional separator, as in `00:00:01.500`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp_str` - The timestamp string, e.g. `"00:00:01,000"`.
+    /// * `lenient` - Whether `.` is accepted as well as `,` before the milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Timestamp, String>` - Returns a new `Timestamp` instance, or an error message if the string is malformed.
+    pub fn from_string_with(timestamp_str: &str, lenient: bool) -> Result<Self, String> {
         let parts: Vec<&str> = timestamp_str.split(':').collect();
         if parts.len() != 3 {
             return Err("Invalid timestamp format".to_string());
@@ -46,7 +72,11 @@ impl Timestamp {
 
         let hours: u32 = parts[0].parse().map_err(|_| "Invalid hours")?;
         let minutes: u32 = parts[1].parse().map_err(|_| "Invalid minutes")?;
-        let seconds_parts: Vec<&str> = parts[2].split(',').collect();
+        let seconds_parts: Vec<&str> = if lenient {
+            parts[2].split([',', '.']).collect()
+        } else {
+            parts[2].split(',').collect()
+        };
         if seconds_parts.len() != 2 {
             return Err("Invalid seconds format".to_string());
         }
@@ -63,23 +93,84 @@ impl Timestamp {
         })
     }
 
+    /// Creates a new `Timestamp` instance from an ASS (SubStation Alpha) timestamp, formatted
+    /// as `H:MM:SS.cc` with centiseconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp_str` - The timestamp string, e.g. `"0:00:01.50"`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Timestamp, String>` - Returns a new `Timestamp` instance, or an error message if the string is malformed.
+    pub fn from_ass(timestamp_str: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = timestamp_str.trim().split(':').collect();
+        if parts.len() != 3 {
+            return Err("Invalid timestamp format".to_string());
+        }
+
+        let hours: u32 = parts[0].parse().map_err(|_| "Invalid hours")?;
+        let minutes: u32 = parts[1].parse().map_err(|_| "Invalid minutes")?;
+        let (seconds, centiseconds) = parts[2].split_once('.').ok_or("Invalid seconds format")?;
+        let seconds: u32 = seconds.parse().map_err(|_| "Invalid seconds")?;
+        if centiseconds.len() != 2 {
+            return Err("Invalid centiseconds".to_string());
+        }
+        let centiseconds: u32 = centiseconds.parse().map_err(|_| "Invalid centiseconds")?;
+
+        Ok(Timestamp {
+            hours,
+            minutes,
+            seconds,
+            milliseconds: centiseconds * 10,
+        })
+    }
+
+    /// Checks whether the timestamp fits the two-digit hours of the SRT format, i.e. is below
+    /// 100 hours. Longer timestamps are still displayed, with wider hours.
+    pub fn is_srt_representable(&self) -> bool {
+        self.hours <= 99
+    }
+
+    /// Returns the hours component of the timestamp.
+    pub fn hours(&self) -> u32 {
+        self.hours
+    }
+
+    /// Returns the minutes component of the timestamp.
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+
+    /// Returns the seconds component of the timestamp.
+    pub fn seconds(&self) -> u32 {
+        self.seconds
+    }
+
+    /// Returns the milliseconds component of the timestamp.
+    pub fn milliseconds(&self) -> u32 {
+        self.milliseconds
+    }
+
     /// Converts the `Timestamp` instance to  milliseconds.
     ///
     /// # Returns
     ///
     /// * `u64` - The timestamp in milliseconds.
     pub fn to_millis(&self) -> u64 {
-        let total_seconds = self.hours * 3600 + self.minutes * 60 + self.seconds;
-        let total_milliseconds = total_seconds * 1000 + self.milliseconds;
-        total_milliseconds as u64
+        let total_seconds =
+            self.hours as u64 * 3600 + self.minutes as u64 * 60 + self.seconds as u64;
+        total_seconds * 1000 + self.milliseconds as u64
     }
 
-    /// Creates a new `Timestamp` instance from the given millis value.
+    /// Creates a new `Timestamp` instance from the given millis value, saturating at
+    /// [`Timestamp::MAX`].
     ///
     /// # Arguments
     ///
     /// * `millis` - The timestamp in milliseconds.
     pub fn from_millis(millis: u64) -> Self {
+        let millis = millis.min(Timestamp::MAX.to_millis());
         let total_seconds = millis / 1000;
         let milliseconds = (millis % 1000) as u32;
         let seconds = (total_seconds % 60) as u32;
@@ -95,6 +186,97 @@ impl Timestamp {
         }
     }
 
+    /// Converts the `Timestamp` instance to a frame number at the given frame rate.
+    /// The result is rounded to the nearest frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `fps` - The frame rate, must be positive.
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The frame number.
+    pub fn to_frames(&self, fps: f64) -> u64 {
+        (self.to_millis() as f64 * fps / 1000.0).round() as u64
+    }
+
+    /// Formats the timestamp as a WebVTT timestamp (`HH:MM:SS.mmm`).
+    pub fn to_vtt(&self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}.{:03}",
+            self.hours, self.minutes, self.seconds, self.milliseconds
+        )
+    }
+
+    /// Formats the timestamp as an ASS (SubStation Alpha) timestamp (`H:MM:SS.cc`).
+    ///
+    /// ASS timestamps have centisecond precision, so the milliseconds are rounded to the
+    /// nearest centisecond, with halves rounded up, e.g. `00:00:01,005` becomes `0:00:01.01`.
+    /// The rounding carries over into the seconds, minutes and hours.
+    pub fn to_ass(&self) -> String {
+        let centiseconds = (self.to_millis() + 5) / 10;
+        format!(
+            "{}:{:02}:{:02}.{:02}",
+            centiseconds / 360_000,
+            centiseconds / 6_000 % 60,
+            centiseconds / 100 % 60,
+            centiseconds % 100
+        )
+    }
+
+    /// Creates a new `Timestamp` instance from a frame number at the given frame rate.
+    /// The result is rounded to the nearest millisecond.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The frame number.
+    /// * `fps` - The frame rate, must be positive.
+    pub fn from_frames(frames: u64, fps: f64) -> Self {
+        Timestamp::from_millis((frames as f64 * 1000.0 / fps).round() as u64)
+    }
+
+    /// Snaps the timestamp to the nearest frame boundary at the given frame rate.
+    /// Halfway cases are rounded away from zero, see [`Timestamp::to_frames`].
+    ///
+    /// # Arguments
+    ///
+    /// * `fps` - The frame rate, must be positive.
+    pub fn round_to_frame(&mut self, fps: f64) {
+        *self = Timestamp::from_frames(self.to_frames(fps), fps);
+    }
+
+    /// Adds a duration to the timestamp, returning `None` if the result would be later than
+    /// [`Timestamp::MAX`]. Unlike [`Timestamp::move_ts`], the timestamp is not modified in
+    /// place.
+    pub fn checked_add(&self, delta: Duration) -> Option<Timestamp> {
+        let delta = u64::try_from(delta.as_millis()).ok()?;
+        self.to_millis()
+            .checked_add(delta)
+            .filter(|&millis| millis <= Timestamp::MAX.to_millis())
+            .map(Timestamp::from_millis)
+    }
+
+    /// Subtracts a duration from the timestamp, returning `None` if the result would be
+    /// negative. Unlike [`Timestamp::move_ts`], the timestamp is not modified in place.
+    pub fn checked_sub(&self, delta: Duration) -> Option<Timestamp> {
+        let delta = u64::try_from(delta.as_millis()).ok()?;
+        self.to_millis()
+            .checked_sub(delta)
+            .map(Timestamp::from_millis)
+    }
+
+    /// Adds a duration to the timestamp, saturating at [`Timestamp::MAX`].
+    pub fn saturating_add(&self, delta: Duration) -> Timestamp {
+        let delta = u64::try_from(delta.as_millis()).unwrap_or(u64::MAX);
+        Timestamp::from_millis(self.to_millis().saturating_add(delta))
+    }
+
+    /// Subtracts a duration from the timestamp, saturating at `00:00:00,000`.
+    pub fn saturating_sub(&self, delta: Duration) -> Timestamp {
+        let delta = u64::try_from(delta.as_millis()).unwrap_or(u64::MAX);
+        Timestamp::from_millis(self.to_millis().saturating_sub(delta))
+    }
+
     /// Moves the timestamp by the given duration in the specified direction.
     ///
     /// # Arguments
@@ -118,16 +300,32 @@ impl Timestamp {
             return Err(SRTError::TimeError("Duration is too large".to_string()));
         }
 
-        let delta = match direction {
-            Direction::Forward => delta.as_millis() as i64,
-            Direction::Backward => -(delta.as_millis() as i64),
-        };
+        let delta = direction.apply(delta.as_millis() as i64);
 
         let new_timestamp = std::cmp::max(total_milliseconds + delta, 0);
         *self = Timestamp::from_millis(new_timestamp as u64);
 
         Ok(())
     }
+
+    /// Computes how far another timestamp is from this one, the reverse of [`Timestamp::move_ts`].
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The timestamp to measure the distance to.
+    ///
+    /// # Returns
+    ///
+    /// * `(Duration, Direction)` - The absolute distance, and whether `other` is forward or
+    ///   backward from this timestamp. Equal timestamps are a zero distance forward.
+    pub fn delta_to(&self, other: &Timestamp) -> (Duration, Direction) {
+        let (from, to) = (self.to_millis(), other.to_millis());
+        if to >= from {
+            (Duration::from_millis(to - from), Direction::Forward)
+        } else {
+            (Duration::from_millis(from - to), Direction::Backward)
+        }
+    }
 }
 
 impl PartialEq for Timestamp {
@@ -148,6 +346,24 @@ impl std::fmt::Display for Timestamp {
         )
     }
 }
+/// Parses a timestamp in the `HH:MM:SS,mmm` format, see [`Timestamp::from_string`].
+///
+/// # Examples
+///
+/// ```rust
+/// use shu_2al::core::timestamp::Timestamp;
+///
+/// let timestamp: Timestamp = "00:00:01,500".parse().unwrap();
+///
+/// assert_eq!(timestamp.to_millis(), 1500);
+/// ```
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timestamp::from_string(s)
+    }
+}
 impl PartialOrd for Timestamp {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -168,6 +384,104 @@ impl Ord for Timestamp {
     }
 }
 
+/// Adding a duration saturates at [`Timestamp::MAX`].
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.saturating_add(rhs)
+    }
+}
+
+/// Subtracting a duration saturates at `00:00:00,000`.
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.saturating_sub(rhs)
+    }
+}
+
+/// The difference between two timestamps, saturating at zero if `rhs` is later than `self`.
+impl Sub<&Timestamp> for &Timestamp {
+    type Output = Duration;
+
+    fn sub(self, rhs: &Timestamp) -> Self::Output {
+        Duration::from_millis(self.to_millis().saturating_sub(rhs.to_millis()))
+    }
+}
+
+impl Sub for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, rhs: Timestamp) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+/// Timestamps are serialized in their SRT string form (`HH:MM:SS,mmm`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Timestamp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let timestamp_str = String::deserialize(deserializer)?;
+        Timestamp::from_string(&timestamp_str).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parses a duration given as a number of milliseconds (`1500ms`), seconds (`2s`) or
+/// minutes (`1m`), or as an SRT timestamp (`00:00:02,000`). A number without a unit is read as
+/// milliseconds.
+///
+/// # Arguments
+///
+/// * `input` - The duration string.
+///
+/// # Returns
+///
+/// * `Result<Duration, String>` - The duration, or an error message if the string is malformed.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use shu_2al::core::timestamp::parse_duration;
+///
+/// assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+/// assert_eq!(parse_duration("00:00:02,000"), Ok(Duration::from_secs(2)));
+/// assert!(parse_duration("2h").is_err());
+/// ```
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    if input.contains(':') {
+        let timestamp = Timestamp::from_string(input)?;
+        return Ok(Duration::from_millis(timestamp.to_millis()));
+    }
+
+    let (number, unit_millis) = if let Some(number) = input.strip_suffix("ms") {
+        (number, 1)
+    } else if let Some(number) = input.strip_suffix('s') {
+        (number, 1_000)
+    } else if let Some(number) = input.strip_suffix('m') {
+        (number, 60_000)
+    } else {
+        (input, 1)
+    };
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("Invalid duration \"{}\"", input))?;
+    number
+        .checked_mul(unit_millis)
+        .map(Duration::from_millis)
+        .ok_or_else(|| format!("Duration \"{}\" is too large", input))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +495,47 @@ mod tests {
         assert_eq!(timestamp.milliseconds, 0);
     }
 
+    #[test]
+    fn test_timestamp_from_string_lenient() {
+        let timestamp = Timestamp::from_string_with("00:00:01.500", true).unwrap();
+        assert_eq!(timestamp.to_millis(), 1500);
+        let timestamp = Timestamp::from_string_with("00:00:01,500", true).unwrap();
+        assert_eq!(timestamp.to_millis(), 1500);
+
+        assert!(Timestamp::from_string("00:00:01.500").is_err());
+        assert!(Timestamp::from_string_with("00:00:01.500", false).is_err());
+        assert!(Timestamp::from_string_with("00:00:01,500.000", true).is_err());
+    }
+
+    #[test]
+    fn test_timestamp_from_ass() {
+        let timestamp = Timestamp::from_ass("1:02:03.45").unwrap();
+        assert_eq!(timestamp.to_string(), "01:02:03,450");
+
+        assert!(Timestamp::from_ass("0:00:01,50").is_err());
+        assert!(Timestamp::from_ass("0:00:01.5").is_err());
+        assert!(Timestamp::from_ass("0:01.50").is_err());
+    }
+
+    #[test]
+    fn test_timestamp_is_srt_representable() {
+        let timestamp = Timestamp::from_millis(360_000_000);
+        assert_eq!(timestamp.to_string(), "100:00:00,000");
+        assert!(!timestamp.is_srt_representable());
+
+        let timestamp = Timestamp::from_string("99:59:59,999").unwrap();
+        assert!(timestamp.is_srt_representable());
+    }
+
+    #[test]
+    fn test_timestamp_getters() {
+        let timestamp = Timestamp::from_string("01:02:03,004").unwrap();
+        assert_eq!(timestamp.hours(), 1);
+        assert_eq!(timestamp.minutes(), 2);
+        assert_eq!(timestamp.seconds(), 3);
+        assert_eq!(timestamp.milliseconds(), 4);
+    }
+
     #[test]
     fn test_timestamp_from_string_invalid() {
         assert!(Timestamp::from_string("00:00:01").is_err());
@@ -193,6 +548,70 @@ mod tests {
         assert_eq!(timestamp.to_string(), "00:00:01,000");
     }
 
+    #[test]
+    fn test_timestamp_from_str() {
+        let timestamp: Timestamp = "00:01:02,003".parse().unwrap();
+        assert_eq!(timestamp, Timestamp::from_string("00:01:02,003").unwrap());
+        assert!("00:01:02".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn test_timestamp_to_vtt() {
+        let timestamp = Timestamp::from_string("01:02:03,045").unwrap();
+        assert_eq!(timestamp.to_vtt(), "01:02:03.045");
+    }
+
+    #[test]
+    fn test_timestamp_to_ass() {
+        let timestamp = Timestamp::from_string("00:00:01,005").unwrap();
+        assert_eq!(timestamp.to_ass(), "0:00:01.01");
+        let timestamp = Timestamp::from_string("00:00:01,004").unwrap();
+        assert_eq!(timestamp.to_ass(), "0:00:01.00");
+        let timestamp = Timestamp::from_string("01:59:59,995").unwrap();
+        assert_eq!(timestamp.to_ass(), "2:00:00.00");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1500ms"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("3m"), Ok(Duration::from_secs(180)));
+        assert_eq!(parse_duration("750"), Ok(Duration::from_millis(750)));
+        assert_eq!(
+            parse_duration("00:01:02,500"),
+            Ok(Duration::from_millis(62_500))
+        );
+
+        assert_eq!(
+            parse_duration("2h"),
+            Err("Invalid duration \"2h\"".to_string())
+        );
+        assert!(parse_duration("1.5s").is_err());
+        assert!(parse_duration("-2s").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn test_timestamp_delta_to() {
+        let start = Timestamp::from_string("00:00:01,500").unwrap();
+        let end = Timestamp::from_string("00:01:02,000").unwrap();
+
+        assert_eq!(
+            start.delta_to(&end),
+            (Duration::from_millis(60_500), Direction::Forward)
+        );
+        assert_eq!(
+            end.delta_to(&start),
+            (Duration::from_millis(60_500), Direction::Backward)
+        );
+        assert_eq!(start.delta_to(&start), (Duration::ZERO, Direction::Forward));
+
+        let (delta, direction) = end.delta_to(&start);
+        let mut moved = end.clone();
+        moved.move_ts(&delta, &direction).unwrap();
+        assert_eq!(moved, start);
+    }
+
     #[test]
     fn test_timestamp_partial_eq() {
         let timestamp1 = Timestamp::from_string("00:00:01,000").unwrap();
@@ -301,4 +720,148 @@ mod tests {
             .unwrap();
         assert_eq!(timestamp.to_string(), "00:00:00,000");
     }
+
+    #[test]
+    fn test_timestamp_to_frames() {
+        let timestamp = Timestamp::from_string("00:00:01,000").unwrap();
+        assert_eq!(timestamp.to_frames(24.0), 24);
+        assert_eq!(timestamp.to_frames(30.0), 30);
+
+        let timestamp = Timestamp::from_string("00:01:00,042").unwrap();
+        assert_eq!(timestamp.to_frames(24.0), 1441);
+    }
+
+    #[test]
+    fn test_timestamp_from_frames() {
+        assert_eq!(Timestamp::from_frames(48, 24.0).to_string(), "00:00:02,000");
+        // 1 frame at 24 fps is 41.666... ms
+        assert_eq!(Timestamp::from_frames(1, 24.0).to_string(), "00:00:00,042");
+        // 1 frame at 30 fps is 33.333... ms
+        assert_eq!(Timestamp::from_frames(1, 30.0).to_string(), "00:00:00,033");
+    }
+
+    #[test]
+    fn test_timestamp_round_to_frame() {
+        // 1010 ms is 25.25 frames at 25 fps
+        let mut timestamp = Timestamp::from_string("00:00:01,010").unwrap();
+        timestamp.round_to_frame(25.0);
+        assert_eq!(timestamp.to_string(), "00:00:01,000");
+
+        // 1020 ms is exactly halfway between frames 25 and 26
+        let mut timestamp = Timestamp::from_string("00:00:01,020").unwrap();
+        timestamp.round_to_frame(25.0);
+        assert_eq!(timestamp.to_string(), "00:00:01,040");
+    }
+
+    #[test]
+    fn test_timestamp_frames_round_trip() {
+        for frames in [0, 1, 2, 29, 1441, 86399] {
+            assert_eq!(Timestamp::from_frames(frames, 24.0).to_frames(24.0), frames);
+            assert_eq!(Timestamp::from_frames(frames, 30.0).to_frames(30.0), frames);
+        }
+    }
+
+    #[test]
+    fn test_timestamp_checked_add() {
+        let timestamp = Timestamp::from_string("00:00:01,000").unwrap();
+        assert_eq!(
+            timestamp.checked_add(Duration::from_millis(1500)),
+            Some(Timestamp::from_string("00:00:02,500").unwrap())
+        );
+        assert_eq!(timestamp.to_string(), "00:00:01,000");
+    }
+
+    #[test]
+    fn test_timestamp_checked_sub() {
+        let timestamp = Timestamp::from_string("00:00:01,000").unwrap();
+        assert_eq!(
+            timestamp.checked_sub(Duration::from_millis(1000)),
+            Some(Timestamp::from_string("00:00:00,000").unwrap())
+        );
+        assert_eq!(timestamp.checked_sub(Duration::from_millis(1001)), None);
+    }
+
+    #[test]
+    fn test_timestamp_saturating() {
+        let timestamp = Timestamp::from_string("00:00:01,000").unwrap();
+        assert_eq!(
+            timestamp.saturating_sub(Duration::from_secs(5)).to_string(),
+            "00:00:00,000"
+        );
+        assert_eq!(
+            timestamp
+                .saturating_add(Duration::from_secs(59))
+                .to_string(),
+            "00:01:00,000"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_overflow() {
+        let timestamp = Timestamp::from_millis(0);
+        assert_eq!(timestamp.saturating_add(Duration::MAX), Timestamp::MAX);
+        assert_eq!(timestamp.checked_add(Duration::MAX), None);
+        assert_eq!(Timestamp::MAX.checked_add(Duration::from_millis(1)), None);
+        assert_eq!(
+            Timestamp::MAX.checked_add(Duration::ZERO),
+            Some(Timestamp::MAX)
+        );
+
+        let long = timestamp
+            .checked_add(Duration::from_secs(5000 * 3600))
+            .unwrap();
+        assert_eq!(long.to_string(), "5000:00:00,000");
+        assert_eq!(long.to_millis(), 5000 * 3_600_000);
+        assert_eq!(Timestamp::from_millis(u64::MAX), Timestamp::MAX);
+    }
+
+    #[test]
+    fn test_timestamp_add_duration() {
+        let timestamp = Timestamp::from_string("00:00:59,500").unwrap();
+        let result = timestamp + Duration::from_millis(700);
+        assert_eq!(result.to_string(), "00:01:00,200");
+    }
+
+    #[test]
+    fn test_timestamp_add_duration_clamped() {
+        let timestamp = Timestamp::from_string("00:00:01,000").unwrap();
+        assert_eq!(timestamp.clone() + Duration::MAX, Timestamp::MAX);
+        assert_eq!(Timestamp::MAX + Duration::from_secs(1), Timestamp::MAX);
+        assert_eq!(
+            (timestamp + Duration::from_secs(5000 * 3600)).to_millis(),
+            5000 * 3_600_000 + 1000
+        );
+    }
+
+    #[test]
+    fn test_timestamp_sub_duration() {
+        let timestamp = Timestamp::from_string("00:01:00,200").unwrap();
+        let result = timestamp - Duration::from_millis(700);
+        assert_eq!(result.to_string(), "00:00:59,500");
+    }
+
+    #[test]
+    fn test_timestamp_sub_duration_clamped() {
+        let timestamp = Timestamp::from_string("00:00:01,000").unwrap();
+        let result = timestamp - Duration::from_secs(5);
+        assert_eq!(result.to_string(), "00:00:00,000");
+    }
+
+    #[test]
+    fn test_timestamp_sub_timestamp() {
+        let timestamp1 = Timestamp::from_string("00:01:02,000").unwrap();
+        let timestamp2 = Timestamp::from_string("00:00:58,500").unwrap();
+        assert_eq!(&timestamp1 - &timestamp2, Duration::from_millis(3500));
+        assert_eq!(timestamp2 - timestamp1, Duration::ZERO);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_timestamp_serde() {
+        let timestamp = Timestamp::from_string("01:02:03,004").unwrap();
+        let json = serde_json::to_string(&timestamp).unwrap();
+        assert_eq!(json, "\"01:02:03,004\"");
+        let parsed: Timestamp = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, timestamp);
+    }
 }
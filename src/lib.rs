@@ -0,0 +1,8 @@
+pub mod batch;
+pub mod core;
+pub mod modules;
+#[cfg(feature = "serde")]
+pub mod pipeline;
+pub mod source;
+#[cfg(feature = "serde")]
+pub mod target;
@@ -1,39 +1,24 @@
-use std::env;
+use std::process::ExitCode;
 
-use core::srt::SRT;
+use clap::Parser;
 
-mod core;
-mod modules;
-mod pipeline;
-mod source;
-mod target;
+use cli::Cli;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 3 {
-        eprintln!("Usage: {} <input_file> <output_file>", args[0]);
-        return;
-    }
+mod cli;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
     // Initialize the subscriber
     tracing_subscriber::fmt()
         .with_ansi(false)
         .with_writer(std::io::stdout)
         .init();
 
-    let input_file = &args[1];
-    let output_file = &args[2];
-
-    let mut srt = SRT::new(input_file);
-    match srt.read_file() {
-        Ok(_) => tracing::debug!("File read successfully!"),
+    match cli::run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
-            tracing::error!("Error reading file: {}", e);
-            return;
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
         }
     }
-
-    match srt.write_file(output_file) {
-        Ok(_) => tracing::info!("File written successfully!"),
-        Err(e) => tracing::error!("Error writing file: {}", e),
-    }
 }
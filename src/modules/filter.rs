@@ -10,13 +10,17 @@ use crate::{core::srt::SRT, modules::module::Module};
 /// * `enabled` - A boolean indicating whether the filter module is enabled.
 /// * `remove_empty_lines` - A boolean indicating whether to remove empty lines from the subtitles.
 /// * `words_list` - A list of words to filter out from the subtitles. If a subtitle contains any of these words, it will be removed.
+/// * `strip_tags` - A boolean indicating whether to strip formatting tags (`<i>`, `<b>`, ...) from the subtitles.
 ///
 /// # Example
 /// ```
+/// use shu_2al::modules::filter::FilterModule;
+///
 /// let filter_module = FilterModule {
 ///     enabled: true,
 ///     remove_empty_lines: true,
 ///     words_list: vec!["test".to_string()],
+///     strip_tags: false,
 /// };
 /// ```
 pub struct FilterModule {
@@ -25,6 +29,9 @@ pub struct FilterModule {
     pub remove_empty_lines: bool,
     /// A list of words to filter out from the subtitles. If a subtitle contains any of these words, it will be removed.
     pub words_list: Vec<String>,
+    /// Whether to strip formatting tags from the subtitles before filtering them.
+    /// Tags are kept by default.
+    pub strip_tags: bool,
 }
 
 impl Module for FilterModule {
@@ -37,6 +44,10 @@ impl Module for FilterModule {
         }
 
         let mut lock = input.lock().unwrap();
+        if self.strip_tags {
+            lock.strip_tags();
+        }
+
         lock.subtitles.retain(|subtitle| {
             if self.remove_empty_lines && subtitle.text.trim().is_empty() {
                 return false;
@@ -52,9 +63,7 @@ impl Module for FilterModule {
         });
 
         // Re-index subtitles after filtering
-        for (index, subtitle) in lock.subtitles.iter_mut().enumerate() {
-            subtitle.index = index + 1;
-        }
+        lock.renumber();
 
         drop(lock);
         Ok(input)
@@ -78,30 +87,35 @@ mod tests {
                 start_time: Timestamp::from_string("00:00:01,000").unwrap(),
                 end_time: Timestamp::from_string("00:00:05,000").unwrap(),
                 text: "Hello, World!".to_string(),
+                settings: None,
             },
             Subtitle {
                 index: 2,
                 start_time: Timestamp::from_string("00:00:06,000").unwrap(),
                 end_time: Timestamp::from_string("00:00:10,000").unwrap(),
                 text: "This is a test.".to_string(),
+                settings: None,
             },
             Subtitle {
                 index: 3,
                 start_time: Timestamp::from_string("00:00:11,000").unwrap(),
                 end_time: Timestamp::from_string("00:00:15,000").unwrap(),
                 text: "Another line.".to_string(),
+                settings: None,
             },
         ];
 
         let srt = SRT {
             subtitles: subtitles.clone(),
             file_path: "test.srt".to_string(),
+            ..Default::default()
         };
 
         let filter_module = FilterModule {
             enabled: true,
             remove_empty_lines: true,
             words_list: vec!["test".to_string()],
+            strip_tags: false,
         };
 
         let input = Arc::new(Mutex::new(srt));
@@ -113,4 +127,64 @@ mod tests {
         assert_eq!(result.lock().unwrap().subtitles[1].text, "Another line.");
         assert_eq!(result.lock().unwrap().subtitles[1].index, 2);
     }
+
+    fn tagged_srt() -> SRT {
+        let subtitles = vec![
+            Subtitle {
+                index: 1,
+                start_time: Timestamp::from_string("00:00:01,000").unwrap(),
+                end_time: Timestamp::from_string("00:00:05,000").unwrap(),
+                text: "<i>Hello, World!</i>".to_string(),
+                settings: None,
+            },
+            Subtitle {
+                index: 2,
+                start_time: Timestamp::from_string("00:00:06,000").unwrap(),
+                end_time: Timestamp::from_string("00:00:10,000").unwrap(),
+                text: "<b>This is a test.</b>".to_string(),
+                settings: None,
+            },
+        ];
+
+        SRT {
+            subtitles,
+            file_path: "test.srt".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_module_preserves_tags() {
+        let filter_module = FilterModule {
+            enabled: true,
+            remove_empty_lines: true,
+            words_list: vec!["test".to_string()],
+            strip_tags: false,
+        };
+
+        let input = Arc::new(Mutex::new(tagged_srt()));
+        let result = filter_module.process(input).unwrap();
+
+        assert_eq!(result.lock().unwrap().subtitles.len(), 1);
+        assert_eq!(
+            result.lock().unwrap().subtitles[0].text,
+            "<i>Hello, World!</i>"
+        );
+    }
+
+    #[test]
+    fn test_filter_module_strips_tags() {
+        let filter_module = FilterModule {
+            enabled: true,
+            remove_empty_lines: true,
+            words_list: vec!["test".to_string()],
+            strip_tags: true,
+        };
+
+        let input = Arc::new(Mutex::new(tagged_srt()));
+        let result = filter_module.process(input).unwrap();
+
+        assert_eq!(result.lock().unwrap().subtitles.len(), 1);
+        assert_eq!(result.lock().unwrap().subtitles[0].text, "Hello, World!");
+    }
 }
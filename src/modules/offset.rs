@@ -66,18 +66,21 @@ mod tests {
                 start_time: Timestamp::from_string("00:00:01,000").unwrap(),
                 end_time: Timestamp::from_string("00:00:05,000").unwrap(),
                 text: "Hello, World!".to_string(),
+                settings: None,
             },
             Subtitle {
                 index: 2,
                 start_time: Timestamp::from_string("00:00:06,000").unwrap(),
                 end_time: Timestamp::from_string("00:00:10,000").unwrap(),
                 text: "This is a test.".to_string(),
+                settings: None,
             },
         ];
 
         let srt = SRT {
             subtitles: subtitles.clone(),
             file_path: "test.srt".to_string(),
+            ..Default::default()
         };
 
         let offset_module = OffsetModule {
@@ -41,12 +41,14 @@ mod tests {
                 start_time: Timestamp::from_string("00:00:01,000").unwrap(),
                 end_time: Timestamp::from_string("00:00:04,000").unwrap(),
                 text: "Hello, world!".to_string(),
+                settings: None,
             },
             Subtitle {
                 index: 2,
                 start_time: Timestamp::from_string("00:00:05,000").unwrap(),
                 end_time: Timestamp::from_string("00:00:08,000").unwrap(),
                 text: "This is a test.".to_string(),
+                settings: None,
             },
         ];
 